@@ -1,35 +1,219 @@
-use bevy::prelude::{Input, KeyCode, Res};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 pub enum GameControl {
     Up,
     Down,
     Left,
     Right,
+    Jump,
+    Attack,
+    Dash,
+    Interact,
+    Pause,
 }
 
 impl GameControl {
-    pub fn pressed(&self, keyboard_input: &Res<Input<KeyCode>>) -> bool {
+    /// All controls, in the order they are listed in the settings
+    pub const ALL: [GameControl; 9] = [
+        GameControl::Up,
+        GameControl::Down,
+        GameControl::Left,
+        GameControl::Right,
+        GameControl::Jump,
+        GameControl::Attack,
+        GameControl::Dash,
+        GameControl::Interact,
+        GameControl::Pause,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameControl::Up => "Up",
+            GameControl::Down => "Down",
+            GameControl::Left => "Left",
+            GameControl::Right => "Right",
+            GameControl::Jump => "Jump",
+            GameControl::Attack => "Attack",
+            GameControl::Dash => "Dash",
+            GameControl::Interact => "Interact",
+            GameControl::Pause => "Pause",
+        }
+    }
+
+    fn default_keys(&self) -> &'static [KeyCode] {
+        match self {
+            GameControl::Up => &[KeyCode::W, KeyCode::Up],
+            GameControl::Down => &[KeyCode::S, KeyCode::Down],
+            GameControl::Left => &[KeyCode::A, KeyCode::Left],
+            GameControl::Right => &[KeyCode::D, KeyCode::Right],
+            GameControl::Jump => &[KeyCode::Up, KeyCode::Space],
+            GameControl::Attack => &[KeyCode::X],
+            GameControl::Dash => &[KeyCode::C, KeyCode::ShiftLeft],
+            GameControl::Interact => &[KeyCode::E],
+            GameControl::Pause => &[KeyCode::Escape, KeyCode::P],
+        }
+    }
+
+    fn gamepad_buttons(&self) -> &'static [GamepadButtonType] {
         match self {
-            GameControl::Up => {
-                keyboard_input.pressed(KeyCode::W) || keyboard_input.pressed(KeyCode::Up)
-            }
-            GameControl::Down => {
-                keyboard_input.pressed(KeyCode::S) || keyboard_input.pressed(KeyCode::Down)
-            }
-            GameControl::Left => {
-                keyboard_input.pressed(KeyCode::A) || keyboard_input.pressed(KeyCode::Left)
-            }
-            GameControl::Right => {
-                keyboard_input.pressed(KeyCode::D) || keyboard_input.pressed(KeyCode::Right)
-            }
+            GameControl::Up => &[GamepadButtonType::DPadUp],
+            GameControl::Down => &[GamepadButtonType::DPadDown],
+            GameControl::Left => &[GamepadButtonType::DPadLeft],
+            GameControl::Right => &[GamepadButtonType::DPadRight],
+            GameControl::Jump => &[GamepadButtonType::South],
+            GameControl::Attack => &[GamepadButtonType::West],
+            GameControl::Dash => &[GamepadButtonType::RightTrigger],
+            GameControl::Interact => &[GamepadButtonType::North],
+            GameControl::Pause => &[GamepadButtonType::Start],
+        }
+    }
+}
+
+/// Keyboard keys bound to each [`GameControl`]
+/// Gamepad buttons are fixed
+#[derive(Clone, Debug, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputBindings {
+    keys: BTreeMap<GameControl, Vec<KeyCode>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        InputBindings {
+            keys: BTreeMap::new(),
+        }
+        .with_defaults()
+    }
+}
+
+impl InputBindings {
+    /// Binds the default keys to all controls that have no binding yet,
+    /// e.g. controls added after the bindings were saved
+    pub fn with_defaults(mut self) -> Self {
+        for control in GameControl::ALL {
+            self.keys
+                .entry(control)
+                .or_insert_with(|| control.default_keys().to_vec());
         }
+        self
+    }
+
+    pub fn keys(&self, control: GameControl) -> &[KeyCode] {
+        self.keys
+            .get(&control)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn rebind(&mut self, control: GameControl, key: KeyCode) {
+        self.keys.insert(control, vec![key]);
+    }
+}
+
+/// Checks the state of [`GameControl`]s on the keyboard and all connected gamepads
+#[derive(SystemParam)]
+pub struct ControlInput<'w> {
+    keyboard_input: Res<'w, Input<KeyCode>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_input: Res<'w, Input<GamepadButton>>,
+    bindings: Res<'w, InputBindings>,
+}
+
+impl<'w> ControlInput<'w> {
+    pub fn pressed(&self, control: GameControl) -> bool {
+        self.bindings
+            .keys(control)
+            .iter()
+            .any(|key| self.keyboard_input.pressed(*key))
+            || self.any_gamepad_button(control, |button| self.gamepad_input.pressed(button))
+    }
+
+    pub fn just_pressed(&self, control: GameControl) -> bool {
+        self.bindings
+            .keys(control)
+            .iter()
+            .any(|key| self.keyboard_input.just_pressed(*key))
+            || self.any_gamepad_button(control, |button| self.gamepad_input.just_pressed(button))
+    }
+
+    /// Like [`Self::pressed`], but ignores the keys and buttons also bound to `other`
+    pub fn pressed_apart_from(&self, control: GameControl, other: GameControl) -> bool {
+        let other_keys = self.bindings.keys(other);
+        let other_buttons = other.gamepad_buttons();
+        self.bindings
+            .keys(control)
+            .iter()
+            .filter(|key| !other_keys.contains(key))
+            .any(|key| self.keyboard_input.pressed(*key))
+            || self.any_gamepad_button(control, |button| {
+                !other_buttons.contains(&button.button_type) && self.gamepad_input.pressed(button)
+            })
+    }
+
+    fn any_gamepad_button(
+        &self,
+        control: GameControl,
+        check: impl Fn(GamepadButton) -> bool,
+    ) -> bool {
+        self.gamepads.iter().any(|gamepad| {
+            control
+                .gamepad_buttons()
+                .iter()
+                .any(|button_type| check(GamepadButton::new(gamepad, *button_type)))
+        })
     }
 }
 
-pub fn get_movement(control: GameControl, input: &Res<Input<KeyCode>>) -> f32 {
-    if control.pressed(input) {
+pub fn get_movement(control: GameControl, input: &ControlInput) -> f32 {
+    if input.pressed(control) {
         1.0
     } else {
         0.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_bind_every_control() {
+        let bindings = InputBindings::default();
+
+        for control in GameControl::ALL {
+            assert_eq!(
+                bindings.keys(control),
+                control.default_keys(),
+                "{control:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn with_defaults_keeps_saved_bindings() {
+        let mut bindings = InputBindings::default();
+        bindings.rebind(GameControl::Jump, KeyCode::K);
+
+        let bindings = bindings.with_defaults();
+
+        assert_eq!(bindings.keys(GameControl::Jump), [KeyCode::K]);
+    }
+
+    #[test]
+    fn with_defaults_binds_controls_missing_from_saved_bindings() {
+        // bindings saved before the interact control existed
+        let saved: InputBindings = ron::from_str("(keys: {Jump: [K]})").unwrap();
+
+        let bindings = saved.with_defaults();
+
+        assert_eq!(bindings.keys(GameControl::Jump), [KeyCode::K]);
+        assert_eq!(
+            bindings.keys(GameControl::Interact),
+            GameControl::Interact.default_keys()
+        );
+    }
+}
@@ -1,44 +1,86 @@
 use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
 
-use crate::actions::game_control::{get_movement, GameControl};
+use crate::actions::game_control::get_movement;
 use crate::player::Player;
-use crate::GameState;
+use crate::GameplayLabel;
+use std::collections::BTreeSet;
 
 mod game_control;
 
+pub use game_control::{ControlInput, GameControl, InputBindings};
+
 pub const FOLLOW_EPSILON: f32 = 5.;
 
 pub struct ActionsPlugin;
 
-// This plugin listens for keyboard input and converts the input into Actions
+// This plugin listens for keyboard, gamepad and touch input and converts the input into Actions
 // Actions can then be used as a resource in other systems to act on the player input.
 impl Plugin for ActionsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Actions>().add_systems(
-            Update,
-            set_movement_actions.run_if(in_state(GameState::Playing)),
-        );
+        app.init_resource::<Actions>()
+            .init_resource::<InputBindings>()
+            .init_resource::<InputLock>()
+            .add_systems(Update, set_movement_actions.in_set(GameplayLabel));
     }
 }
 
 #[derive(Default, Resource)]
 pub struct Actions {
     pub player_movement: Option<Vec2>,
+    pub jump: bool,
+    /// Jump was pressed this frame, for jumps that must not repeat while it is held
+    pub jump_started: bool,
+    /// Jump is only held through keys also bound to up, which climbs ladders instead
+    pub jump_from_up: bool,
+    pub attack: bool,
+    pub dash: bool,
+    pub interact: bool,
+}
+
+/// Why the player's input is ignored at the moment
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub enum LockReason {
+    Dialogue,
+    Cutscene,
+}
+
+/// Keeps all [`Actions`] at their defaults while any reason to lock the input is held
+#[derive(Default, Resource)]
+pub struct InputLock(BTreeSet<LockReason>);
+
+impl InputLock {
+    pub fn lock(&mut self, reason: LockReason) {
+        self.0.insert(reason);
+    }
+
+    pub fn unlock(&mut self, reason: LockReason) {
+        self.0.remove(&reason);
+    }
+
+    pub fn is_locked(&self) -> bool {
+        !self.0.is_empty()
+    }
 }
 
 pub fn set_movement_actions(
     mut actions: ResMut<Actions>,
-    keyboard_input: Res<Input<KeyCode>>,
+    input_lock: Res<InputLock>,
+    control_input: ControlInput,
     touch_input: Res<Touches>,
     player: Query<&Transform, With<Player>>,
     camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
 ) {
+    if input_lock.is_locked() {
+        *actions = Actions::default();
+        return;
+    }
+
     let mut player_movement = Vec2::new(
-        get_movement(GameControl::Right, &keyboard_input)
-            - get_movement(GameControl::Left, &keyboard_input),
-        get_movement(GameControl::Up, &keyboard_input)
-            - get_movement(GameControl::Down, &keyboard_input),
+        get_movement(GameControl::Right, &control_input)
+            - get_movement(GameControl::Left, &control_input),
+        get_movement(GameControl::Up, &control_input)
+            - get_movement(GameControl::Down, &control_input),
     );
 
     if let Some(touch_position) = touch_input.first_pressed_position() {
@@ -57,4 +99,11 @@ pub fn set_movement_actions(
     } else {
         actions.player_movement = None;
     }
+    actions.jump = control_input.pressed(GameControl::Jump);
+    actions.jump_started = control_input.just_pressed(GameControl::Jump);
+    actions.jump_from_up =
+        actions.jump && !control_input.pressed_apart_from(GameControl::Jump, GameControl::Up);
+    actions.attack = control_input.pressed(GameControl::Attack);
+    actions.dash = control_input.just_pressed(GameControl::Dash);
+    actions.interact = control_input.just_pressed(GameControl::Interact);
 }
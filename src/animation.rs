@@ -1,4 +1,5 @@
 use crate::from_component::{FromComponentLabel, FromComponentPlugin};
+use crate::PauseState;
 use std::{marker::PhantomData, ops::Range};
 
 use bevy::prelude::*;
@@ -13,13 +14,25 @@ pub struct SpriteSheetAnimation {
     pub repeat: bool,
 }
 
+/// Plays the entity's [`SpriteSheetAnimation`] faster or slower, 1 is the normal speed
+#[derive(Clone, Copy, Debug, Component)]
+pub struct AnimationSpeed(pub f32);
+
 pub fn sprite_sheet_animation(
-    mut query: Query<(Entity, &mut TextureAtlasSprite, &mut SpriteSheetAnimation)>,
+    mut query: Query<(
+        Entity,
+        &mut TextureAtlasSprite,
+        &mut SpriteSheetAnimation,
+        Option<&AnimationSpeed>,
+    )>,
     time: Res<Time>,
     mut event_writer: EventWriter<AnimationEvent>,
 ) {
-    for (entity, mut sprite, mut sprite_sheet_animation) in query.iter_mut() {
-        sprite_sheet_animation.frame_timer.tick(time.delta());
+    for (entity, mut sprite, mut sprite_sheet_animation, speed) in query.iter_mut() {
+        let speed = speed.map_or(1., |speed| speed.0.max(0.));
+        sprite_sheet_animation
+            .frame_timer
+            .tick(time.delta().mul_f32(speed));
 
         if sprite_sheet_animation.frame_timer.just_finished() {
             sprite.index += 1;
@@ -59,7 +72,8 @@ impl Plugin for SpriteSheetAnimationPlugin {
                 Update,
                 sprite_sheet_animation
                     .in_set(AnimationLabel)
-                    .after(FromComponentLabel),
+                    .after(FromComponentLabel)
+                    .run_if(in_state(PauseState::Running)),
             )
             .add_systems(
                 Update,
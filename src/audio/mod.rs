@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+
+mod music;
+mod settings;
+mod sfx;
+mod spatial;
+
+pub use music::{Music, MusicTrack};
+pub use settings::{AudioBus, AudioSettings};
+pub use sfx::{EmitterSfxEvent, SfxEvent};
+pub use spatial::AudioEmitter;
+
+pub struct InternalAudioPlugin;
+
+// This plugin is responsible to control the game audio
+impl Plugin for InternalAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioSettings>().add_plugins((
+            AudioPlugin,
+            sfx::SfxPlugin,
+            music::MusicPlugin,
+            spatial::SpatialAudioPlugin,
+        ));
+    }
+}
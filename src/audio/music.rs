@@ -0,0 +1,135 @@
+use crate::audio::{AudioBus, AudioSettings};
+use crate::loading::AudioAssets;
+use crate::{GameState, PauseState};
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use serde::Deserialize;
+use std::time::Duration;
+
+const MUSIC_VOLUME: f64 = 0.5;
+/// Share of the music volume that is kept while the music is ducked
+const DUCKED_VOLUME: f64 = 0.3;
+const CROSSFADE: Duration = Duration::from_millis(1500);
+const DUCK_FADE: Duration = Duration::from_millis(300);
+
+pub struct MusicPlugin;
+
+/// This plugin plays the background music on its own audio channel
+/// Which track is playing is controlled through the [`Music`] resource
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_channel::<MusicChannel>()
+            .init_resource::<Music>()
+            .init_resource::<PlayingMusic>()
+            .add_systems(OnEnter(GameState::Menu), play_menu_music)
+            .add_systems(OnEnter(PauseState::Paused), duck_music)
+            .add_systems(OnExit(PauseState::Paused), unduck_music)
+            .add_systems(
+                Update,
+                update_music
+                    .run_if(resource_exists::<AudioAssets>())
+                    .run_if(
+                        resource_changed::<Music>().or_else(resource_changed::<AudioSettings>()),
+                    ),
+            );
+    }
+}
+
+/// Audio channel dedicated to background music
+#[derive(Resource)]
+pub struct MusicChannel;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Deserialize)]
+pub enum MusicTrack {
+    Menu,
+    Level07,
+    Boss,
+}
+
+impl MusicTrack {
+    fn source(&self, audio_assets: &AudioAssets) -> Handle<AudioSource> {
+        match self {
+            MusicTrack::Menu => audio_assets.menu_music.clone(),
+            MusicTrack::Level07 => audio_assets.level07_music.clone(),
+            MusicTrack::Boss => audio_assets.boss_music.clone(),
+        }
+    }
+
+    /// Position in seconds the track jumps back to when it reaches its end.
+    /// Everything before it is an intro that is only played once.
+    fn loop_start(&self) -> f64 {
+        match self {
+            MusicTrack::Menu => 0.0,
+            MusicTrack::Level07 => 8.0,
+            MusicTrack::Boss => 4.0,
+        }
+    }
+}
+
+/// The music that should currently be playing
+/// Changing the track crossfades from the old one to the new one
+#[derive(Default, Resource)]
+pub struct Music {
+    pub track: Option<MusicTrack>,
+    /// Lower the music volume, e.g. while a menu is open on top of the game
+    pub ducked: bool,
+}
+
+#[derive(Default, Resource)]
+struct PlayingMusic {
+    track: Option<MusicTrack>,
+    instance: Option<Handle<AudioInstance>>,
+}
+
+fn play_menu_music(mut music: ResMut<Music>) {
+    music.track = Some(MusicTrack::Menu);
+    music.ducked = false;
+}
+
+fn duck_music(mut music: ResMut<Music>) {
+    music.ducked = true;
+}
+
+fn unduck_music(mut music: ResMut<Music>) {
+    music.ducked = false;
+}
+
+fn update_music(
+    music: Res<Music>,
+    settings: Res<AudioSettings>,
+    mut playing: ResMut<PlayingMusic>,
+    audio_assets: Res<AudioAssets>,
+    channel: Res<AudioChannel<MusicChannel>>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    let mut volume = MUSIC_VOLUME * settings.volume(AudioBus::Music);
+    if music.ducked {
+        volume *= DUCKED_VOLUME;
+    }
+
+    if playing.track == music.track {
+        if let Some(instance) = &playing.instance {
+            if let Some(instance) = audio_instances.get_mut(instance) {
+                instance.set_volume(volume, AudioTween::linear(DUCK_FADE));
+            }
+        }
+        return;
+    }
+
+    if let Some(instance) = playing.instance.take() {
+        if let Some(instance) = audio_instances.get_mut(&instance) {
+            instance.stop(AudioTween::linear(CROSSFADE));
+        }
+    }
+
+    playing.track = music.track;
+    if let Some(track) = music.track {
+        let instance = channel
+            .play(track.source(&audio_assets))
+            .loop_from(track.loop_start())
+            .with_volume(volume)
+            .fade_in(AudioTween::linear(CROSSFADE))
+            .handle();
+        playing.instance = Some(instance);
+    }
+}
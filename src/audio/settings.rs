@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum AudioBus {
+    Master,
+    Music,
+    Sfx,
+}
+
+/// Volume levels chosen by the user, each in the range `0.0..=1.0`
+#[derive(Clone, Debug, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings {
+            master: 0.8,
+            music: 0.7,
+            sfx: 0.8,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn level(&self, bus: AudioBus) -> f32 {
+        match bus {
+            AudioBus::Master => self.master,
+            AudioBus::Music => self.music,
+            AudioBus::Sfx => self.sfx,
+        }
+    }
+
+    pub fn set_level(&mut self, bus: AudioBus, level: f32) {
+        let level = level.clamp(0., 1.);
+        match bus {
+            AudioBus::Master => self.master = level,
+            AudioBus::Music => self.music = level,
+            AudioBus::Sfx => self.sfx = level,
+        }
+    }
+
+    /// The volume sounds on the given bus should be scaled with, taking master volume and mute into account
+    pub fn volume(&self, bus: AudioBus) -> f64 {
+        if self.muted {
+            return 0.;
+        }
+        match bus {
+            AudioBus::Master => self.master as f64,
+            bus => (self.master * self.level(bus)) as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volume_scales_buses_with_master() {
+        let settings = AudioSettings {
+            master: 0.5,
+            music: 0.5,
+            sfx: 1.,
+            muted: false,
+        };
+        assert_eq!(settings.volume(AudioBus::Master), 0.5);
+        assert_eq!(settings.volume(AudioBus::Music), 0.25);
+        assert_eq!(settings.volume(AudioBus::Sfx), 0.5);
+    }
+
+    #[test]
+    fn muted_silences_all_buses() {
+        let settings = AudioSettings {
+            muted: true,
+            ..default()
+        };
+        for bus in [AudioBus::Master, AudioBus::Music, AudioBus::Sfx] {
+            assert_eq!(settings.volume(bus), 0.);
+        }
+    }
+
+    #[test]
+    fn set_level_clamps() {
+        let mut settings = AudioSettings::default();
+        settings.set_level(AudioBus::Music, 1.5);
+        assert_eq!(settings.level(AudioBus::Music), 1.);
+        settings.set_level(AudioBus::Sfx, -0.5);
+        assert_eq!(settings.level(AudioBus::Sfx), 0.);
+    }
+}
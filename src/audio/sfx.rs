@@ -0,0 +1,278 @@
+use crate::audio::spatial::{update_emitters, update_listener, AudioEmitter, AudioListener};
+use crate::audio::{AudioBus, AudioSettings};
+use crate::loading::AudioAssets;
+use crate::{GameState, PauseState};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_kira_audio::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+pub struct SfxPlugin;
+
+/// This plugin plays one-shot sound effects on their own audio channel
+/// Other plugins request a sound by sending an [`SfxEvent`], or an [`EmitterSfxEvent`] for positional sounds
+impl Plugin for SfxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_channel::<SfxChannel>()
+            .add_event::<SfxEvent>()
+            .add_event::<EmitterSfxEvent>()
+            .init_resource::<SfxVoices>()
+            .add_systems(OnEnter(PauseState::Paused), pause_sfx)
+            .add_systems(OnExit(PauseState::Paused), resume_sfx)
+            .add_systems(OnExit(GameState::Playing), stop_sfx)
+            .add_systems(
+                Update,
+                (
+                    play_sfx
+                        .after(update_listener)
+                        .before(update_emitters)
+                        .run_if(resource_exists::<AudioAssets>()),
+                    apply_sfx_volume
+                        .before(update_emitters)
+                        .run_if(resource_changed::<AudioSettings>()),
+                ),
+            );
+    }
+}
+
+/// Audio channel dedicated to sound effects
+#[derive(Resource)]
+pub struct SfxChannel;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Event, Deserialize)]
+pub enum SfxEvent {
+    Jump,
+    Land,
+    Swing,
+    Hit,
+    Footstep,
+    Pickup,
+    Dash,
+    Splash,
+    /// Retracting spikes coming out
+    Spikes,
+    /// A falling block or crusher hitting the ground
+    Crash,
+}
+
+/// Plays a sound effect at the position of an entity with an [`AudioEmitter`]
+#[derive(Copy, Clone, Debug, Event)]
+pub struct EmitterSfxEvent {
+    pub sfx: SfxEvent,
+    pub emitter: Entity,
+}
+
+/// Playback rules for a single sound effect
+struct SfxSpec {
+    volume: f64,
+    /// Relative amount the volume may randomly deviate by, e.g. `0.1` is +/- 10%
+    volume_jitter: f64,
+    /// Relative amount the playback rate (and thereby pitch) may randomly deviate by
+    pitch_jitter: f64,
+    /// Maximum number of instances of this sound playing at the same time
+    max_voices: usize,
+    /// Minimum number of seconds between two instances of this sound
+    cooldown: f32,
+}
+
+impl SfxEvent {
+    fn source(&self, audio_assets: &AudioAssets) -> Handle<AudioSource> {
+        match self {
+            SfxEvent::Jump => audio_assets.jump.clone(),
+            SfxEvent::Land => audio_assets.land.clone(),
+            SfxEvent::Swing => audio_assets.swing.clone(),
+            SfxEvent::Hit => audio_assets.hit.clone(),
+            SfxEvent::Footstep => audio_assets.footstep.clone(),
+            SfxEvent::Pickup => audio_assets.pickup.clone(),
+            SfxEvent::Dash => audio_assets.dash.clone(),
+            SfxEvent::Splash => audio_assets.splash.clone(),
+            SfxEvent::Spikes => audio_assets.spikes.clone(),
+            SfxEvent::Crash => audio_assets.crash.clone(),
+        }
+    }
+
+    fn spec(&self) -> SfxSpec {
+        match self {
+            SfxEvent::Jump => SfxSpec {
+                volume: 0.5,
+                volume_jitter: 0.1,
+                pitch_jitter: 0.05,
+                max_voices: 1,
+                cooldown: 0.1,
+            },
+            SfxEvent::Land => SfxSpec {
+                volume: 0.5,
+                volume_jitter: 0.15,
+                pitch_jitter: 0.08,
+                max_voices: 1,
+                cooldown: 0.15,
+            },
+            SfxEvent::Swing => SfxSpec {
+                volume: 0.6,
+                volume_jitter: 0.1,
+                pitch_jitter: 0.1,
+                max_voices: 2,
+                cooldown: 0.2,
+            },
+            SfxEvent::Hit => SfxSpec {
+                volume: 0.7,
+                volume_jitter: 0.1,
+                pitch_jitter: 0.1,
+                max_voices: 4,
+                cooldown: 0.05,
+            },
+            SfxEvent::Footstep => SfxSpec {
+                volume: 0.3,
+                volume_jitter: 0.2,
+                pitch_jitter: 0.15,
+                max_voices: 2,
+                cooldown: 0.28,
+            },
+            SfxEvent::Pickup => SfxSpec {
+                volume: 0.6,
+                volume_jitter: 0.05,
+                pitch_jitter: 0.05,
+                max_voices: 3,
+                cooldown: 0.0,
+            },
+            SfxEvent::Dash => SfxSpec {
+                volume: 0.6,
+                volume_jitter: 0.1,
+                pitch_jitter: 0.08,
+                max_voices: 1,
+                cooldown: 0.2,
+            },
+            SfxEvent::Splash => SfxSpec {
+                volume: 0.6,
+                volume_jitter: 0.15,
+                pitch_jitter: 0.1,
+                max_voices: 2,
+                cooldown: 0.1,
+            },
+            SfxEvent::Spikes => SfxSpec {
+                volume: 0.4,
+                volume_jitter: 0.1,
+                pitch_jitter: 0.1,
+                max_voices: 4,
+                cooldown: 0.05,
+            },
+            SfxEvent::Crash => SfxSpec {
+                volume: 0.8,
+                volume_jitter: 0.1,
+                pitch_jitter: 0.1,
+                max_voices: 3,
+                cooldown: 0.1,
+            },
+        }
+    }
+}
+
+/// A playing instance with the volume it was started at, before the bus volume is applied
+struct VoiceInstance {
+    handle: Handle<AudioInstance>,
+    volume: f64,
+}
+
+#[derive(Default)]
+struct SfxVoice {
+    instances: Vec<VoiceInstance>,
+    last_played: Option<f32>,
+}
+
+#[derive(Default, Resource)]
+struct SfxVoices(HashMap<SfxEvent, SfxVoice>);
+
+fn play_sfx(
+    mut events: EventReader<SfxEvent>,
+    mut emitter_events: EventReader<EmitterSfxEvent>,
+    mut voices: ResMut<SfxVoices>,
+    mut emitters: Query<(&GlobalTransform, &mut AudioEmitter)>,
+    listener: Res<AudioListener>,
+    audio_assets: Res<AudioAssets>,
+    settings: Res<AudioSettings>,
+    channel: Res<AudioChannel<SfxChannel>>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_seconds();
+    let bus_volume = settings.volume(AudioBus::Sfx);
+    let mut rng = rand::thread_rng();
+
+    let requests = events.read().map(|sfx| (*sfx, None)).chain(
+        emitter_events
+            .read()
+            .map(|event| (event.sfx, Some(event.emitter))),
+    );
+    for (sfx, emitter) in requests {
+        let spec = sfx.spec();
+        let voice = voices.0.entry(sfx).or_default();
+
+        voice
+            .instances
+            .retain(|instance| !matches!(channel.state(&instance.handle), PlaybackState::Stopped));
+        if voice.instances.len() >= spec.max_voices {
+            continue;
+        }
+        if let Some(last_played) = voice.last_played {
+            if now - last_played < spec.cooldown {
+                continue;
+            }
+        }
+
+        let volume = spec.volume * (1.0 + rng.gen_range(-spec.volume_jitter..=spec.volume_jitter));
+        let playback_rate = 1.0 + rng.gen_range(-spec.pitch_jitter..=spec.pitch_jitter);
+        let mut command = channel.play(sfx.source(&audio_assets));
+        command.with_playback_rate(playback_rate);
+
+        let emitter = match emitter {
+            Some(emitter) => emitters.get_mut(emitter).ok(),
+            None => None,
+        };
+        let handle = if let Some((transform, mut emitter)) = emitter {
+            let (attenuation, panning) =
+                listener.spatialize(transform.translation().truncate(), &emitter);
+            command
+                .with_volume(bus_volume * volume * attenuation)
+                .with_panning(panning);
+            let instance = command.handle();
+            emitter.attach(instance.clone(), volume);
+            instance
+        } else {
+            command.with_volume(bus_volume * volume);
+            command.handle()
+        };
+
+        voice.instances.push(VoiceInstance { handle, volume });
+        voice.last_played = Some(now);
+    }
+}
+
+fn pause_sfx(channel: Res<AudioChannel<SfxChannel>>) {
+    channel.pause();
+}
+
+fn resume_sfx(channel: Res<AudioChannel<SfxChannel>>) {
+    channel.resume();
+}
+
+fn stop_sfx(channel: Res<AudioChannel<SfxChannel>>, mut voices: ResMut<SfxVoices>) {
+    channel.stop();
+    voices.0.clear();
+}
+
+/// Rescale sound effects that are still playing when the volume settings change
+/// Sounds played by an [`AudioEmitter`] are rescaled by `update_emitters` afterwards
+fn apply_sfx_volume(
+    voices: Res<SfxVoices>,
+    settings: Res<AudioSettings>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    let bus_volume = settings.volume(AudioBus::Sfx);
+    for voice in voices.0.values() {
+        for instance in &voice.instances {
+            if let Some(audio_instance) = audio_instances.get_mut(&instance.handle) {
+                audio_instance.set_volume(bus_volume * instance.volume, AudioTween::default());
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::audio::sfx::SfxChannel;
+use crate::audio::{AudioBus, AudioSettings};
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use bevy_parallax::ParallaxCameraComponent;
+
+/// How far sounds get pushed to the side, `1.0` would play them on a single speaker only
+const PAN_STRENGTH: f32 = 0.8;
+
+pub struct SpatialAudioPlugin;
+
+/// This plugin pans and attenuates sounds attached to an [`AudioEmitter`]
+/// depending on where the emitter is relative to the camera
+impl Plugin for SpatialAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioListener>()
+            .add_systems(Update, (update_listener, update_emitters).chain());
+    }
+}
+
+/// Makes sounds played through an [`EmitterSfxEvent`](crate::audio::EmitterSfxEvent)
+/// pan and fade with the distance of this entity to the camera
+#[derive(Component)]
+pub struct AudioEmitter {
+    /// Distance up to which sounds play at full volume
+    pub inner_radius: f32,
+    /// Distance from which on sounds are silent
+    pub outer_radius: f32,
+    sounds: Vec<EmittedSound>,
+}
+
+struct EmittedSound {
+    instance: Handle<AudioInstance>,
+    volume: f64,
+}
+
+impl AudioEmitter {
+    pub fn new(inner_radius: f32, outer_radius: f32) -> Self {
+        AudioEmitter {
+            inner_radius,
+            outer_radius,
+            sounds: vec![],
+        }
+    }
+
+    pub(super) fn attach(&mut self, instance: Handle<AudioInstance>, volume: f64) {
+        self.sounds.push(EmittedSound { instance, volume });
+    }
+}
+
+/// Center of the visible area, from where emitters are heard
+#[derive(Resource)]
+pub(super) struct AudioListener {
+    position: Vec2,
+    half_width: f32,
+}
+
+impl Default for AudioListener {
+    fn default() -> Self {
+        AudioListener {
+            position: Vec2::ZERO,
+            half_width: 1.,
+        }
+    }
+}
+
+impl AudioListener {
+    /// Returns the volume factor and the panning for a sound played at `position`
+    pub(super) fn spatialize(&self, position: Vec2, emitter: &AudioEmitter) -> (f64, f64) {
+        let offset = position - self.position;
+        let falloff = ((offset.length() - emitter.inner_radius)
+            / (emitter.outer_radius - emitter.inner_radius).max(f32::EPSILON))
+        .clamp(0., 1.);
+        let attenuation = (1. - falloff).powi(2);
+        let panning = 0.5 + 0.5 * PAN_STRENGTH * (offset.x / self.half_width).clamp(-1., 1.);
+
+        (attenuation as f64, panning as f64)
+    }
+}
+
+pub(super) fn update_listener(
+    mut listener: ResMut<AudioListener>,
+    camera: Query<(&GlobalTransform, &OrthographicProjection), With<ParallaxCameraComponent>>,
+) {
+    let Ok((transform, projection)) = camera.get_single() else {
+        return;
+    };
+    listener.position = transform.translation().truncate() + projection.area.center();
+    listener.half_width = (projection.area.width() / 2.).max(1.);
+}
+
+pub(super) fn update_emitters(
+    listener: Res<AudioListener>,
+    settings: Res<AudioSettings>,
+    channel: Res<AudioChannel<SfxChannel>>,
+    mut emitters: Query<(&GlobalTransform, &mut AudioEmitter)>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    let bus_volume = settings.volume(AudioBus::Sfx);
+    for (transform, mut emitter) in &mut emitters {
+        emitter
+            .sounds
+            .retain(|sound| !matches!(channel.state(&sound.instance), PlaybackState::Stopped));
+        if emitter.sounds.is_empty() {
+            continue;
+        }
+
+        let (attenuation, panning) =
+            listener.spatialize(transform.translation().truncate(), &emitter);
+        for sound in &emitter.sounds {
+            if let Some(instance) = audio_instances.get_mut(&sound.instance) {
+                instance.set_volume(
+                    bus_volume * sound.volume * attenuation,
+                    AudioTween::default(),
+                );
+                instance.set_panning(panning, AudioTween::default());
+            }
+        }
+    }
+}
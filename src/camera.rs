@@ -1,11 +1,121 @@
 use crate::player::Player;
 use crate::states::Level;
-use bevy::prelude::{
-    Entity, EventWriter, OrthographicProjection, Query, Transform, Vec2, With, Without,
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_parallax::{
+    CreateParallaxEvent, LayerData, LayerRepeat, LayerSpeed, ParallaxCameraComponent,
+    ParallaxMoveEvent, RepeatStrategy,
 };
-use bevy_parallax::ParallaxMoveEvent;
 
 const ASPECT_RATIO: f32 = 16. / 9.;
+/// Oscillations per second of a camera shake
+const SHAKE_FREQUENCY: f32 = 30.;
+
+pub struct CameraPlugin;
+
+/// This plugin spawns the camera and the parallax background once loading is done
+/// Both are shared by all following states and never despawned
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraShake>()
+            .init_resource::<CameraBounds>()
+            .add_systems(OnExit(GameState::Loading), setup_camera)
+            .add_systems(OnEnter(GameState::Playing), reset_camera_effects);
+    }
+}
+
+/// Shakes the camera up and down, fading out over the shake's duration
+#[derive(Default, Resource)]
+pub struct CameraShake {
+    timer: Option<Timer>,
+    strength: f32,
+}
+
+impl CameraShake {
+    /// `strength` is the initial amplitude in pixels
+    pub fn start(&mut self, duration: f32, strength: f32) {
+        self.timer = Some(Timer::from_seconds(duration, TimerMode::Once));
+        self.strength = strength;
+    }
+
+    fn offset(&mut self, delta: std::time::Duration) -> f32 {
+        let Some(timer) = &mut self.timer else {
+            return 0.;
+        };
+        timer.tick(delta);
+        if timer.finished() {
+            self.timer = None;
+            return 0.;
+        }
+        (timer.elapsed_secs() * SHAKE_FREQUENCY * std::f32::consts::TAU).sin()
+            * self.strength
+            * timer.percent_left()
+    }
+}
+
+/// Horizontal world range the camera's view is locked inside, e.g. during an ambush
+#[derive(Default, Resource)]
+pub struct CameraBounds(pub Option<(f32, f32)>);
+
+fn reset_camera_effects(mut shake: ResMut<CameraShake>, mut bounds: ResMut<CameraBounds>) {
+    *shake = CameraShake::default();
+    bounds.0 = None;
+}
+
+fn setup_camera(mut commands: Commands, mut create_parallax: EventWriter<CreateParallaxEvent>) {
+    let camera = commands
+        .spawn(Camera2dBundle::default())
+        .insert(ParallaxCameraComponent::default())
+        .id();
+
+    create_parallax.send(CreateParallaxEvent {
+        layers_data: vec![
+            LayerData {
+                speed: LayerSpeed::Bidirectional(0.9, 0.9),
+                repeat: LayerRepeat::horizontally(RepeatStrategy::Mirror),
+                path: "textures/Level07_Scene01_Sky.png".to_string(),
+                tile_size: Vec2::new(1824.0, 112.0),
+                cols: 1,
+                rows: 1,
+                z: -2.,
+                position: Vec2::new(0., 144. - (112.0 / 2.)),
+                ..default()
+            },
+            LayerData {
+                speed: LayerSpeed::Bidirectional(0.7, 0.7),
+                repeat: LayerRepeat::horizontally(RepeatStrategy::Mirror),
+                path: "textures/Level07_Scene01_Buildings02.png".to_string(),
+                tile_size: Vec2::new(2416.0, 112.0),
+                cols: 1,
+                rows: 1,
+                z: -1.5,
+                position: Vec2::new(0., 144. - (112.0 / 2.)),
+                ..default()
+            },
+            LayerData {
+                speed: LayerSpeed::Bidirectional(0.6, 0.8),
+                repeat: LayerRepeat::horizontally(RepeatStrategy::Mirror),
+                path: "textures/Level07_Scene01_Buildings01.png".to_string(),
+                tile_size: Vec2::new(2416.0, 248.0),
+                z: -0.5,
+                position: Vec2::new(0., 144. - (248.0 / 2.)),
+                ..default()
+            },
+            LayerData {
+                speed: LayerSpeed::Bidirectional(0.3, 0.4),
+                repeat: LayerRepeat::horizontally(RepeatStrategy::Mirror),
+                path: "textures/Level07_Scene01_Trees.png".to_string(),
+                tile_size: Vec2::new(168.0, 136.0),
+                cols: 1,
+                rows: 1,
+                z: -0.3,
+                position: Vec2::new(0., 0.),
+                ..default()
+            },
+        ],
+        camera,
+    });
+}
 
 struct LevelDims {
     /// Height of the level in pixels
@@ -29,7 +139,11 @@ pub fn camera_fit_inside_current_level(
     player_query: Query<&Transform, With<Player>>,
     level_query: Query<(&Transform, &Level), (Without<OrthographicProjection>, Without<Player>)>,
     mut move_event_writer: EventWriter<ParallaxMoveEvent>,
+    time: Res<Time>,
+    mut shake: ResMut<CameraShake>,
+    bounds: Res<CameraBounds>,
 ) {
+    let shake_offset = shake.offset(time.delta());
     if let Ok(Transform {
         translation: player_translation,
         ..
@@ -54,7 +168,10 @@ pub fn camera_fit_inside_current_level(
                 orthographic_projection.scaling_mode =
                     bevy::render::camera::ScalingMode::Fixed { width, height };
 
-                camera_transform.translation.y = -level.px_hei as f32 / 2.;
+                camera_transform.translation.y = -level.px_hei as f32 / 2. + shake_offset;
+                let (left, right) = bounds
+                    .0
+                    .unwrap_or((-level.px_wid as f32 / 2., level.px_wid as f32 / 2.));
 
                 // camera_transform.translation.x =
                 //     (player_translation.x - level_transform.translation.x - width / 2.)
@@ -67,14 +184,13 @@ pub fn camera_fit_inside_current_level(
                     } else {
                         delta + THRESHOLD
                     };
-                    if camera_transform.translation.x + move_by < -level.px_wid as f32 / 2. {
+                    if camera_transform.translation.x + move_by < left {
                         // info!("far left condition met");
-                        move_by = (-level.px_wid as f32 / 2.) - camera_transform.translation.x;
+                        move_by = left - camera_transform.translation.x;
                     }
-                    if camera_transform.translation.x + move_by > level.px_wid as f32 / 2. - width {
+                    if camera_transform.translation.x + move_by > right - width {
                         // info!("far right condition met");
-                        move_by =
-                            (level.px_wid as f32 / 2.) - camera_transform.translation.x - width;
+                        move_by = right - camera_transform.translation.x - width;
                     }
                     move_event_writer.send(ParallaxMoveEvent {
                         camera_move_speed: Vec2::new(move_by, 0.),
@@ -89,7 +205,8 @@ pub fn camera_fit_inside_current_level(
                     bevy::render::camera::ScalingMode::Fixed { width, height };
                 camera_transform.translation.y =
                     (player_translation.y - level_transform.translation.y - height / 2.)
-                        .clamp(0., level.px_hei as f32 - height);
+                        .clamp(0., level.px_hei as f32 - height)
+                        + shake_offset;
                 camera_transform.translation.x = 0.;
             }
 
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+pub struct CharacterPlugin;
+
+/// This plugin keeps each character's [`KinematicCharacterController`] in sync with its [`SlopeSettings`]
+impl Plugin for CharacterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_slope_settings);
+    }
+}
+
+/// How a character moves on sloped ground
+#[derive(Component, Clone, Copy, Debug)]
+pub struct SlopeSettings {
+    /// Slopes steeper than this block the character like a wall, in radians
+    pub max_climb_angle: f32,
+    /// The character slides down slopes steeper than this, in radians
+    pub min_slide_angle: f32,
+    /// Pixels the character may be pulled down to stay on the ground when walking down a slope
+    pub snap_to_ground: Option<f32>,
+}
+
+impl Default for SlopeSettings {
+    fn default() -> Self {
+        SlopeSettings {
+            max_climb_angle: 50_f32.to_radians(),
+            min_slide_angle: 35_f32.to_radians(),
+            snap_to_ground: Some(8.),
+        }
+    }
+}
+
+fn apply_slope_settings(
+    mut characters: Query<
+        (&SlopeSettings, &mut KinematicCharacterController),
+        Changed<SlopeSettings>,
+    >,
+) {
+    for (settings, mut controller) in &mut characters {
+        controller.max_slope_climb_angle = settings.max_climb_angle;
+        controller.min_slope_slide_angle = settings.min_slide_angle;
+        controller.snap_to_ground = settings.snap_to_ground.map(CharacterLength::Absolute);
+    }
+}
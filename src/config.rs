@@ -0,0 +1,62 @@
+use crate::actions::InputBindings;
+use crate::audio::AudioSettings;
+use crate::states::GameplaySettings;
+use crate::storage::{Location, Storage};
+use crate::ui::SettingsMenu;
+use crate::video::VideoSettings;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const CONFIG_KEY: &str = "config";
+
+pub struct ConfigPlugin;
+
+/// This plugin loads the user config on startup and inserts its parts as resources
+/// The config is written back whenever the settings menu is closed
+impl Plugin for ConfigPlugin {
+    fn build(&self, app: &mut App) {
+        let storage = Storage::open(Location::Config);
+        let config = UserConfig::load(&storage);
+        app.insert_resource(ConfigStorage(storage))
+            .insert_resource(config.audio)
+            .insert_resource(config.video)
+            .insert_resource(config.gameplay)
+            .insert_resource(config.controls.with_defaults())
+            .add_systems(OnExit(SettingsMenu::Open), save_config);
+    }
+}
+
+#[derive(Resource)]
+struct ConfigStorage(Storage);
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct UserConfig {
+    audio: AudioSettings,
+    video: VideoSettings,
+    gameplay: GameplaySettings,
+    controls: InputBindings,
+}
+
+impl UserConfig {
+    fn load(storage: &Storage) -> Self {
+        // nothing saved yet on the first start
+        storage.load(CONFIG_KEY).unwrap_or_default()
+    }
+}
+
+fn save_config(
+    storage: Res<ConfigStorage>,
+    audio: Res<AudioSettings>,
+    video: Res<VideoSettings>,
+    gameplay: Res<GameplaySettings>,
+    controls: Res<InputBindings>,
+) {
+    let config = UserConfig {
+        audio: audio.clone(),
+        video: video.clone(),
+        gameplay: gameplay.clone(),
+        controls: controls.clone(),
+    };
+    storage.0.store(CONFIG_KEY, &config);
+}
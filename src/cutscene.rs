@@ -0,0 +1,202 @@
+use crate::actions::{InputLock, LockReason};
+use crate::dialogue::{ActiveDialogue, DialogueLabel, StartDialogue};
+use crate::player::{Player, PlayerAnimationState, PlayerLabel, PLAYER_VELOCITY_X};
+use crate::states::CurrentLevel;
+use crate::{GameState, GameplayLabel};
+use bevy::prelude::*;
+use bevy_parallax::ParallaxMoveEvent;
+use bevy_rapier2d::control::KinematicCharacterController;
+use serde::Deserialize;
+
+/// How close the player has to get to the target of a [`CutsceneStep::MovePlayer`]
+const ARRIVE_DISTANCE: f32 = 2.;
+
+pub struct CutscenePlugin;
+
+/// This plugin plays the cutscenes of the current level, started by a [`StartCutscene`] event
+/// While an [`ActiveCutscene`] exists, the player's movement, jumps and attacks
+/// and the camera following the player are switched off
+impl Plugin for CutscenePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<StartCutscene>()
+            .add_systems(
+                Update,
+                (start_cutscene, run_cutscene)
+                    .chain()
+                    .after(PlayerLabel)
+                    .before(DialogueLabel)
+                    .in_set(GameplayLabel),
+            )
+            .add_systems(OnExit(GameState::Playing), stop_cutscene);
+    }
+}
+
+/// Plays the cutscene with this id from the level data
+#[derive(Event)]
+pub struct StartCutscene(pub String);
+
+/// One step of a cutscene's timeline, the next step starts once it is done
+#[derive(Clone, Deserialize)]
+pub enum CutsceneStep {
+    /// Centers the camera on the world x coordinate over `duration` seconds
+    PanCamera { x: f32, duration: f32 },
+    /// Walks the player to the world x coordinate
+    MovePlayer { x: f32 },
+    PlayAnimation {
+        animation: PlayerAnimationState,
+        duration: f32,
+    },
+    /// Shows the dialogue with this id and waits until it is closed
+    Dialogue(String),
+    /// Seconds to wait
+    Wait(f32),
+}
+
+/// The cutscene playing right now
+#[derive(Resource)]
+pub struct ActiveCutscene {
+    steps: Vec<CutsceneStep>,
+    step: usize,
+    /// Seconds since the current step started
+    elapsed: f32,
+    /// Center of the camera's view when the current pan started
+    pan_from: Option<f32>,
+    dialogue_started: bool,
+}
+
+impl ActiveCutscene {
+    fn next_step(&mut self) {
+        self.step += 1;
+        self.elapsed = 0.;
+        self.pan_from = None;
+        self.dialogue_started = false;
+    }
+}
+
+fn start_cutscene(
+    mut commands: Commands,
+    mut events: EventReader<StartCutscene>,
+    mut input_lock: ResMut<InputLock>,
+    level: CurrentLevel,
+    active: Option<Res<ActiveCutscene>>,
+) {
+    let Some(StartCutscene(id)) = events.read().last() else {
+        return;
+    };
+    if active.is_some() {
+        return;
+    }
+    let Some(steps) = level.data().cutscenes.get(id) else {
+        warn!("There is no cutscene {id}");
+        return;
+    };
+    commands.insert_resource(ActiveCutscene {
+        steps: steps.clone(),
+        step: 0,
+        elapsed: 0.,
+        pan_from: None,
+        dialogue_started: false,
+    });
+    input_lock.lock(LockReason::Cutscene);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_cutscene(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut input_lock: ResMut<InputLock>,
+    mut dialogue: EventWriter<StartDialogue>,
+    mut move_events: EventWriter<ParallaxMoveEvent>,
+    active_dialogue: Option<Res<ActiveDialogue>>,
+    active: Option<ResMut<ActiveCutscene>>,
+    mut players: Query<
+        (
+            &Transform,
+            &mut KinematicCharacterController,
+            &mut PlayerAnimationState,
+        ),
+        With<Player>,
+    >,
+    cameras: Query<(Entity, &Transform, &OrthographicProjection), Without<Player>>,
+) {
+    let Some(mut active) = active else {
+        return;
+    };
+    let Some(step) = active.steps.get(active.step).cloned() else {
+        commands.remove_resource::<ActiveCutscene>();
+        input_lock.unlock(LockReason::Cutscene);
+        return;
+    };
+    active.elapsed += time.delta_seconds();
+
+    let done = match step {
+        CutsceneStep::PanCamera { x, duration } => {
+            let Ok((camera, transform, projection)) = cameras.get_single() else {
+                return;
+            };
+            // the camera's viewport origin is its left edge
+            let center = transform.translation.x + projection.area.width() * 0.5;
+            let from = *active.pan_from.get_or_insert(center);
+            let t = (active.elapsed / duration.max(f32::EPSILON)).min(1.);
+            let target = from + (x - from) * t * t * (3. - 2. * t);
+            move_events.send(ParallaxMoveEvent {
+                camera_move_speed: Vec2::new(target - center, 0.),
+                camera,
+            });
+            t >= 1.
+        }
+        CutsceneStep::MovePlayer { x } => {
+            let Ok((transform, mut controller, mut animation_state)) = players.get_single_mut()
+            else {
+                return;
+            };
+            let distance = x - transform.translation.x;
+            let arrived = distance.abs() <= ARRIVE_DISTANCE;
+            let step =
+                distance.signum() * (PLAYER_VELOCITY_X * time.delta_seconds()).min(distance.abs());
+            let y = controller
+                .translation
+                .map_or(0., |translation| translation.y);
+            controller.translation = Some(Vec2::new(if arrived { 0. } else { step }, y));
+            let animation = if arrived {
+                PlayerAnimationState::Idle
+            } else {
+                PlayerAnimationState::Running
+            };
+            if *animation_state != animation {
+                *animation_state = animation;
+            }
+            arrived
+        }
+        CutsceneStep::PlayAnimation {
+            animation,
+            duration,
+        } => {
+            if let Ok((_, _, mut animation_state)) = players.get_single_mut() {
+                if *animation_state != animation {
+                    *animation_state = animation;
+                }
+            }
+            active.elapsed >= duration
+        }
+        CutsceneStep::Dialogue(id) => {
+            if active.dialogue_started {
+                // dialogues are started in the following systems, so a missing one ends the step
+                active_dialogue.is_none()
+            } else {
+                dialogue.send(StartDialogue(id));
+                active.dialogue_started = true;
+                false
+            }
+        }
+        CutsceneStep::Wait(seconds) => active.elapsed >= seconds,
+    };
+    if done {
+        active.next_step();
+    }
+}
+
+fn stop_cutscene(mut commands: Commands, mut input_lock: ResMut<InputLock>) {
+    commands.remove_resource::<ActiveCutscene>();
+    input_lock.unlock(LockReason::Cutscene);
+}
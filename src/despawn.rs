@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+/// Entities with this component are despawned, including their children,
+/// when the State `S` is exited
+/// The state has to be registered with a [`DespawnOnExitPlugin`]
+#[derive(Component)]
+pub struct DespawnOnExit<S: States>(pub S);
+
+pub struct DespawnOnExitPlugin<S: States>(pub S);
+
+impl<S: States> Plugin for DespawnOnExitPlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(self.0.clone()), despawn_on_exit(self.0.clone()));
+    }
+
+    // the plugin is added once for every state that should clean up after itself
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
+
+fn despawn_on_exit<S: States>(
+    state: S,
+) -> impl FnMut(Commands, Query<(Entity, &DespawnOnExit<S>)>) {
+    move |mut commands, query| {
+        for (entity, despawn_on_exit) in &query {
+            if despawn_on_exit.0 == state {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
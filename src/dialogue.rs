@@ -0,0 +1,421 @@
+use crate::actions::{set_movement_actions, ControlInput, GameControl, InputLock, LockReason};
+use crate::loading::LevelAssets;
+use crate::ron_asset::{RonAsset, RonAssetLoader};
+use crate::save::Progress;
+use crate::{GameState, GameplayLabel};
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+/// Speed of the typewriter effect
+const CHARACTERS_PER_SECOND: f32 = 40.;
+/// Name of the node every dialogue starts at
+const START_NODE: &str = "start";
+
+pub struct DialoguePlugin;
+
+/// This plugin runs the dialogue started by a [`StartDialogue`] event
+/// The player's input is locked while an [`ActiveDialogue`] exists
+impl Plugin for DialoguePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<DialogueData>()
+            .init_asset_loader::<RonAssetLoader<DialogueData>>()
+            .add_event::<StartDialogue>()
+            .add_systems(
+                Update,
+                (advance_dialogue, start_dialogue)
+                    .chain()
+                    // closing a dialogue must not count as an interaction in the same frame
+                    .after(set_movement_actions)
+                    .in_set(DialogueLabel)
+                    .in_set(GameplayLabel),
+            )
+            .add_systems(OnExit(GameState::Playing), close_dialogue);
+    }
+}
+
+/// Systems starting and advancing the [`ActiveDialogue`]
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct DialogueLabel;
+
+/// Asks for the dialogue with this id to be shown
+#[derive(Event)]
+pub struct StartDialogue(pub String);
+
+/// All dialogues of a level by id, loaded from a `.dialogue.ron` file
+#[derive(Asset, TypePath, Deserialize)]
+pub struct DialogueData {
+    pub dialogues: HashMap<String, Dialogue>,
+}
+
+/// A dialogue tree, it starts at the node named "start"
+#[derive(Clone, Deserialize)]
+pub struct Dialogue {
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct DialogueNode {
+    pub lines: Vec<DialogueLine>,
+    /// Flags set in the player's progress when the node is reached
+    #[serde(default)]
+    pub set_flags: Vec<String>,
+    #[serde(default)]
+    pub next: DialogueNext,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct DialogueLine {
+    pub speaker: String,
+    /// Asset path of the speaker's portrait
+    #[serde(default)]
+    pub portrait: Option<String>,
+    pub text: String,
+    /// The line is skipped if the condition does not hold
+    #[serde(default)]
+    pub condition: Option<Condition>,
+}
+
+/// Where a dialogue goes after the last line of a node
+#[derive(Clone, Default, Deserialize)]
+pub enum DialogueNext {
+    #[default]
+    End,
+    Goto(String),
+    /// Lets the player pick, choices whose condition does not hold are hidden
+    Choices(Vec<DialogueChoice>),
+    /// Goes to `then` if the condition holds and to `otherwise` if not
+    Branch {
+        condition: Condition,
+        then: String,
+        otherwise: String,
+    },
+}
+
+#[derive(Clone, Deserialize)]
+pub struct DialogueChoice {
+    pub text: String,
+    /// Node the choice leads to, ends the dialogue if `None`
+    #[serde(default)]
+    pub next: Option<String>,
+    #[serde(default)]
+    pub set_flags: Vec<String>,
+    #[serde(default)]
+    pub condition: Option<Condition>,
+}
+
+/// A check against the story flags in the player's progress
+#[derive(Clone, Deserialize)]
+pub enum Condition {
+    Flag(String),
+    NotFlag(String),
+}
+
+impl Condition {
+    fn holds(&self, flags: &BTreeSet<String>) -> bool {
+        match self {
+            Condition::Flag(flag) => flags.contains(flag),
+            Condition::NotFlag(flag) => !flags.contains(flag),
+        }
+    }
+}
+
+fn holds(condition: &Option<Condition>, flags: &BTreeSet<String>) -> bool {
+    condition
+        .as_ref()
+        .map_or(true, |condition| condition.holds(flags))
+}
+
+impl RonAsset for DialogueData {
+    const EXTENSIONS: &'static [&'static str] = &["dialogue.ron"];
+}
+
+/// The dialogue shown right now, with the lines and choices of the current node
+/// whose conditions held when the node was reached
+#[derive(Resource)]
+pub struct ActiveDialogue {
+    dialogue: Dialogue,
+    lines: Vec<DialogueLine>,
+    choices: Vec<DialogueChoice>,
+    next: DialogueNext,
+    line: usize,
+    /// Characters of the current line the typewriter revealed so far
+    typed: f32,
+    selected: usize,
+}
+
+impl ActiveDialogue {
+    pub fn line(&self) -> Option<&DialogueLine> {
+        self.lines.get(self.line)
+    }
+
+    /// The part of the current line revealed so far
+    pub fn typed_text(&self) -> &str {
+        let Some(line) = self.line() else {
+            return "";
+        };
+        let end = line
+            .text
+            .char_indices()
+            .nth(self.typed as usize)
+            .map_or(line.text.len(), |(index, _)| index);
+        &line.text[..end]
+    }
+
+    fn fully_typed(&self) -> bool {
+        self.line().map_or(true, |line| {
+            self.typed as usize >= line.text.chars().count()
+        })
+    }
+
+    /// Choices are offered once the last line of a node is fully typed
+    pub fn choices(&self) -> &[DialogueChoice] {
+        if self.line + 1 >= self.lines.len() && self.fully_typed() {
+            &self.choices
+        } else {
+            &[]
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Moves on to the node with the given name, returns `false` if the dialogue is over
+    fn enter_node(&mut self, name: &str, flags: &mut BTreeSet<String>) -> bool {
+        let Some(node) = self.dialogue.nodes.get(name).cloned() else {
+            warn!("Ending dialogue at unknown node {name}");
+            return false;
+        };
+        flags.extend(node.set_flags.iter().cloned());
+        self.lines = node
+            .lines
+            .into_iter()
+            .filter(|line| holds(&line.condition, flags))
+            .collect();
+        self.choices = match &node.next {
+            DialogueNext::Choices(choices) => choices
+                .iter()
+                .filter(|choice| holds(&choice.condition, flags))
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.next = node.next;
+        self.line = 0;
+        self.typed = 0.;
+        self.selected = 0;
+        if !self.lines.is_empty() || !self.choices.is_empty() {
+            return true;
+        }
+        // nothing to show in this node
+        let next = self.next.clone();
+        self.follow(&next, flags)
+    }
+
+    /// Follows `next` after the last line of a node without choices
+    fn follow(&mut self, next: &DialogueNext, flags: &mut BTreeSet<String>) -> bool {
+        match next {
+            DialogueNext::End | DialogueNext::Choices(_) => false,
+            DialogueNext::Goto(node) => self.enter_node(node, flags),
+            DialogueNext::Branch {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let node = if condition.holds(flags) {
+                    then
+                } else {
+                    otherwise
+                };
+                self.enter_node(node, flags)
+            }
+        }
+    }
+}
+
+fn start_dialogue(
+    mut commands: Commands,
+    mut events: EventReader<StartDialogue>,
+    mut progress: ResMut<Progress>,
+    mut input_lock: ResMut<InputLock>,
+    level_assets: Res<LevelAssets>,
+    dialogues: Res<Assets<DialogueData>>,
+    active: Option<Res<ActiveDialogue>>,
+) {
+    let Some(StartDialogue(id)) = events.read().last() else {
+        return;
+    };
+    if active.is_some() {
+        return;
+    }
+    let Some(dialogue) = dialogues
+        .get(progress.level.dialogue(&level_assets))
+        .and_then(|data| data.dialogues.get(id))
+    else {
+        warn!("There is no dialogue {id}");
+        return;
+    };
+    let mut active = ActiveDialogue {
+        dialogue: dialogue.clone(),
+        lines: Vec::new(),
+        choices: Vec::new(),
+        next: DialogueNext::End,
+        line: 0,
+        typed: 0.,
+        selected: 0,
+    };
+    if active.enter_node(START_NODE, &mut progress.flags) {
+        commands.insert_resource(active);
+        input_lock.lock(LockReason::Dialogue);
+    }
+}
+
+fn advance_dialogue(
+    mut commands: Commands,
+    time: Res<Time>,
+    control_input: ControlInput,
+    mut progress: ResMut<Progress>,
+    mut input_lock: ResMut<InputLock>,
+    active: Option<ResMut<ActiveDialogue>>,
+) {
+    let Some(mut active) = active else {
+        return;
+    };
+    active.typed += CHARACTERS_PER_SECOND * time.delta_seconds();
+
+    let choice_count = active.choices().len();
+    if choice_count > 0 {
+        if control_input.just_pressed(GameControl::Down) {
+            active.selected = (active.selected + 1) % choice_count;
+        } else if control_input.just_pressed(GameControl::Up) {
+            active.selected = (active.selected + choice_count - 1) % choice_count;
+        }
+    }
+
+    let confirm = control_input.just_pressed(GameControl::Interact)
+        || control_input.just_pressed(GameControl::Jump);
+    if !confirm {
+        return;
+    }
+    if !active.fully_typed() {
+        active.typed = f32::MAX;
+        return;
+    }
+
+    let running = if active.line + 1 < active.lines.len() {
+        active.line += 1;
+        active.typed = 0.;
+        true
+    } else if choice_count > 0 {
+        let choice = active.choices[active.selected].clone();
+        progress.flags.extend(choice.set_flags);
+        choice
+            .next
+            .is_some_and(|node| active.enter_node(&node, &mut progress.flags))
+    } else {
+        let next = active.next.clone();
+        active.follow(&next, &mut progress.flags)
+    };
+    if !running {
+        commands.remove_resource::<ActiveDialogue>();
+        input_lock.unlock(LockReason::Dialogue);
+    }
+}
+
+fn close_dialogue(mut commands: Commands, mut input_lock: ResMut<InputLock>) {
+    commands.remove_resource::<ActiveDialogue>();
+    input_lock.unlock(LockReason::Dialogue);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIALOGUE: &str = r#"(nodes: {
+        "start": (
+            lines: [
+                (speaker: "Fox", text: "Hello"),
+                (speaker: "Fox", text: "Welcome back", condition: Some(Flag("met"))),
+                (speaker: "Fox", text: "Who are you?", condition: Some(NotFlag("met"))),
+            ],
+            set_flags: ["greeted"],
+            next: Choices([
+                (text: "Bye"),
+                (text: "Help", next: Some("help"), condition: Some(Flag("met"))),
+            ]),
+        ),
+        "empty": (lines: [], next: Branch(condition: Flag("met"), then: "help", otherwise: "start")),
+        "help": (lines: [(speaker: "Fox", text: "Follow me")]),
+    })"#;
+
+    fn dialogue() -> ActiveDialogue {
+        ActiveDialogue {
+            dialogue: ron::from_str(DIALOGUE).unwrap(),
+            lines: Vec::new(),
+            choices: Vec::new(),
+            next: DialogueNext::End,
+            line: 0,
+            typed: 0.,
+            selected: 0,
+        }
+    }
+
+    fn texts(lines: &[DialogueLine]) -> Vec<&str> {
+        lines.iter().map(|line| line.text.as_str()).collect()
+    }
+
+    #[test]
+    fn conditions_check_flags() {
+        let flags = BTreeSet::from(["met".to_string()]);
+
+        assert!(Condition::Flag("met".to_string()).holds(&flags));
+        assert!(!Condition::Flag("helped".to_string()).holds(&flags));
+        assert!(!Condition::NotFlag("met".to_string()).holds(&flags));
+        assert!(Condition::NotFlag("helped".to_string()).holds(&flags));
+        assert!(holds(&None, &flags));
+    }
+
+    #[test]
+    fn enter_node_hides_lines_and_choices_whose_condition_fails() {
+        let mut active = dialogue();
+        let mut flags = BTreeSet::new();
+
+        assert!(active.enter_node(START_NODE, &mut flags));
+
+        assert_eq!(texts(&active.lines), ["Hello", "Who are you?"]);
+        assert_eq!(active.choices.len(), 1);
+        assert_eq!(active.choices[0].text, "Bye");
+    }
+
+    #[test]
+    fn enter_node_sets_flags_before_checking_conditions() {
+        let mut active = dialogue();
+        let mut flags = BTreeSet::from(["met".to_string()]);
+
+        assert!(active.enter_node(START_NODE, &mut flags));
+
+        assert!(flags.contains("greeted"));
+        assert_eq!(texts(&active.lines), ["Hello", "Welcome back"]);
+        assert_eq!(active.choices.len(), 2);
+    }
+
+    #[test]
+    fn enter_node_follows_empty_nodes() {
+        let mut active = dialogue();
+        let mut flags = BTreeSet::from(["met".to_string()]);
+
+        assert!(active.enter_node("empty", &mut flags));
+
+        assert_eq!(texts(&active.lines), ["Follow me"]);
+    }
+
+    #[test]
+    fn enter_node_ends_at_unknown_nodes() {
+        let mut active = dialogue();
+
+        assert!(!active.enter_node("missing", &mut BTreeSet::new()));
+    }
+}
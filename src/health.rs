@@ -0,0 +1,107 @@
+use crate::audio::SfxEvent;
+use crate::GameplayLabel;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Seconds a hit protects from further hits
+const HIT_INVULNERABILITY: f32 = 1.;
+/// How often per second invulnerable sprites blink
+const BLINK_RATE: f32 = 10.;
+
+pub struct HealthPlugin;
+
+/// This plugin applies [`Damage`] to entities with [`Health`]
+/// Hits make the target [`Invulnerable`] for a moment, running out of health marks it as [`Dead`]
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Damage>().add_systems(
+            Update,
+            (apply_damage, tick_invulnerability)
+                .chain()
+                .in_set(GameplayLabel),
+        );
+    }
+}
+
+/// Remaining hit points
+#[derive(Component)]
+pub struct Health(pub u32);
+
+#[derive(Event, Clone, Copy, Debug)]
+pub enum Damage {
+    /// Takes `amount` hit points, unless the target is invulnerable
+    Hit { target: Entity, amount: u32 },
+    /// Kills the target regardless of its health, e.g. when falling out of the level
+    Kill { target: Entity },
+}
+
+/// Hits are ignored until the timer finished, the sprite blinks meanwhile
+#[derive(Component)]
+pub struct Invulnerable(pub Timer);
+
+impl Invulnerable {
+    pub fn new(seconds: f32) -> Self {
+        Invulnerable(Timer::from_seconds(seconds, TimerMode::Once))
+    }
+}
+
+/// Inserted once [`Health`] dropped to zero
+#[derive(Component)]
+pub struct Dead;
+
+fn apply_damage(
+    mut commands: Commands,
+    mut damage: EventReader<Damage>,
+    mut sfx: EventWriter<SfxEvent>,
+    mut targets: Query<(&mut Health, Has<Invulnerable>), Without<Dead>>,
+) {
+    // the invulnerability of a hit is only inserted at the end of the frame
+    let mut hit = HashSet::new();
+    for damage in damage.read() {
+        let (target, amount) = match *damage {
+            Damage::Hit { target, amount } => (target, Some(amount)),
+            Damage::Kill { target } => (target, None),
+        };
+        let Ok((mut health, invulnerable)) = targets.get_mut(target) else {
+            continue;
+        };
+        if health.0 == 0 {
+            // already died from an earlier hit this frame
+            continue;
+        }
+        match amount {
+            Some(_) if invulnerable || !hit.insert(target) => continue,
+            Some(amount) => health.0 = health.0.saturating_sub(amount),
+            None => health.0 = 0,
+        }
+        sfx.send(SfxEvent::Hit);
+        if health.0 == 0 {
+            commands
+                .entity(target)
+                .remove::<Invulnerable>()
+                .insert(Dead);
+        } else {
+            commands
+                .entity(target)
+                .insert(Invulnerable::new(HIT_INVULNERABILITY));
+        }
+    }
+}
+
+fn tick_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Invulnerable, Option<&mut TextureAtlasSprite>)>,
+) {
+    for (entity, mut invulnerable, sprite) in &mut query {
+        invulnerable.0.tick(time.delta());
+        let finished = invulnerable.0.finished();
+        if let Some(mut sprite) = sprite {
+            let visible = finished || (invulnerable.0.elapsed_secs() * BLINK_RATE) as u32 % 2 == 0;
+            sprite.color.set_a(if visible { 1. } else { 0.3 });
+        }
+        if finished {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
@@ -2,26 +2,47 @@
 
 mod actions;
 mod audio;
+mod character;
+mod config;
+mod cutscene;
+mod health;
 mod loading;
 mod menu;
 mod player;
+mod save;
 mod states;
+mod storage;
+mod ui;
+mod video;
 
 mod animation;
 mod camera;
+mod despawn;
+mod dialogue;
 mod from_component;
+mod ron_asset;
 
 use crate::actions::ActionsPlugin;
 use crate::audio::InternalAudioPlugin;
+use crate::camera::CameraPlugin;
+use crate::character::CharacterPlugin;
+use crate::config::ConfigPlugin;
+use crate::cutscene::CutscenePlugin;
+use crate::despawn::DespawnOnExitPlugin;
+use crate::dialogue::DialoguePlugin;
+use crate::health::HealthPlugin;
 use crate::loading::LoadingPlugin;
 use crate::menu::MenuPlugin;
 use crate::player::PlayerPlugin;
+use crate::save::SavePlugin;
+use crate::ui::{LoadMenu, SettingsMenu, UiPlugin};
+use crate::video::VideoPlugin;
 
-use crate::states::LevelPlugin;
+use crate::states::{LevelPlugin, OneWayPlatformHooks};
 use bevy::app::App;
 use bevy::prelude::*;
 use bevy_parallax::ParallaxPlugin;
-use bevy_rapier2d::plugin::{NoUserData, RapierPhysicsPlugin};
+use bevy_rapier2d::plugin::RapierPhysicsPlugin;
 use bevy_rapier2d::prelude::RapierDebugRenderPlugin;
 #[cfg(debug_assertions)]
 use bevy_screen_diagnostics::{ScreenDiagnosticsPlugin, ScreenFrameDiagnosticsPlugin};
@@ -38,24 +59,68 @@ enum GameState {
     Playing,
     // Here the menu is drawn and waiting for player interaction
     Menu,
+    // The player ran out of lives
+    GameOver,
+    // Passed through for a single frame to tear the level down and set it up again
+    Restarting,
 }
 
+// Pausing is kept apart from `GameState`, so that opening the pause menu
+// does not count as leaving `GameState::Playing`
+#[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
+enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Systems driving the game world
+/// They only run during `GameState::Playing` while the game is not paused
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+struct GameplayLabel;
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_state::<GameState>().add_plugins((
-            RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(200.0),
-            RapierDebugRenderPlugin::default(),
-            LoadingPlugin,
-            MenuPlugin,
-            ActionsPlugin,
-            InternalAudioPlugin,
-            PlayerPlugin,
-            LevelPlugin,
-            animation::SpriteSheetAnimationPlugin,
-            ParallaxPlugin,
-        ));
+        app.add_state::<GameState>()
+            .add_state::<PauseState>()
+            .configure_sets(
+                Update,
+                GameplayLabel
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(in_state(PauseState::Running)),
+            )
+            .add_plugins((
+                RapierPhysicsPlugin::<OneWayPlatformHooks>::pixels_per_meter(200.0),
+                RapierDebugRenderPlugin::default(),
+                ParallaxPlugin,
+            ))
+            .add_plugins((
+                LoadingPlugin,
+                CameraPlugin,
+                ConfigPlugin,
+                VideoPlugin,
+                UiPlugin,
+                MenuPlugin,
+                ActionsPlugin,
+                InternalAudioPlugin,
+                PlayerPlugin,
+                CharacterPlugin,
+                SavePlugin,
+                LevelPlugin,
+                HealthPlugin,
+                animation::SpriteSheetAnimationPlugin,
+            ))
+            .add_plugins((DialoguePlugin, CutscenePlugin))
+            .add_plugins((
+                DespawnOnExitPlugin(GameState::Menu),
+                DespawnOnExitPlugin(GameState::Playing),
+                DespawnOnExitPlugin(GameState::GameOver),
+                DespawnOnExitPlugin(PauseState::Paused),
+                DespawnOnExitPlugin(SettingsMenu::Open),
+                DespawnOnExitPlugin(LoadMenu::Open),
+            ));
 
         #[cfg(debug_assertions)]
         {
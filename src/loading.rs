@@ -1,3 +1,5 @@
+use crate::dialogue::DialogueData;
+use crate::states::LevelData;
 use crate::GameState;
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
@@ -17,7 +19,8 @@ impl Plugin for LoadingPlugin {
                     "textures/april.assets.ron",
                 )
                 .load_collection::<AudioAssets>()
-                .load_collection::<TextureAssets>(),
+                .load_collection::<TextureAssets>()
+                .load_collection::<LevelAssets>(),
         );
     }
 }
@@ -27,8 +30,32 @@ impl Plugin for LoadingPlugin {
 
 #[derive(AssetCollection, Resource)]
 pub struct AudioAssets {
-    #[asset(path = "audio/flying.ogg")]
-    pub flying: Handle<AudioSource>,
+    #[asset(path = "audio/music/menu.wav")]
+    pub menu_music: Handle<AudioSource>,
+    #[asset(path = "audio/music/level07.wav")]
+    pub level07_music: Handle<AudioSource>,
+    #[asset(path = "audio/music/boss.wav")]
+    pub boss_music: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/jump.wav")]
+    pub jump: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/land.wav")]
+    pub land: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/swing.wav")]
+    pub swing: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/hit.wav")]
+    pub hit: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/footstep.wav")]
+    pub footstep: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/pickup.wav")]
+    pub pickup: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/dash.wav")]
+    pub dash: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/splash.wav")]
+    pub splash: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/spikes.wav")]
+    pub spikes: Handle<AudioSource>,
+    #[asset(path = "audio/sfx/crash.wav")]
+    pub crash: Handle<AudioSource>,
 }
 
 #[derive(AssetCollection, Resource)]
@@ -49,4 +76,16 @@ pub struct TextureAssets {
     pub sky: Handle<Image>,
     #[asset(path = "textures/Level07_Scene01_Trees.png")]
     pub tree: Handle<Image>,
+    #[asset(path = "textures/berry.png")]
+    pub berry: Handle<Image>,
+    #[asset(path = "textures/heart.png")]
+    pub heart: Handle<Image>,
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct LevelAssets {
+    #[asset(path = "levels/level07.level.ron")]
+    pub level07: Handle<LevelData>,
+    #[asset(path = "dialogue/level07.dialogue.ron")]
+    pub level07_dialogue: Handle<DialogueData>,
 }
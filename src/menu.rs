@@ -1,10 +1,8 @@
+use crate::despawn::DespawnOnExit;
 use crate::loading::TextureAssets;
+use crate::ui::{spawn_button, ButtonColors, LoadMenu, MenuRoot, SettingsMenu};
 use crate::GameState;
 use bevy::prelude::*;
-use bevy_parallax::{
-    CreateParallaxEvent, LayerData, LayerRepeat, LayerSpeed, ParallaxCameraComponent,
-    RepeatStrategy,
-};
 
 pub struct MenuPlugin;
 
@@ -13,92 +11,12 @@ pub struct MenuPlugin;
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Menu), setup_menu)
-            .add_systems(Update, click_play_button.run_if(in_state(GameState::Menu)))
-            .add_systems(OnExit(GameState::Menu), cleanup_menu);
+            .add_systems(Update, click_play_button.run_if(in_state(GameState::Menu)));
     }
 }
 
-#[derive(Component)]
-struct ButtonColors {
-    normal: Color,
-    hovered: Color,
-}
-
-impl Default for ButtonColors {
-    fn default() -> Self {
-        ButtonColors {
-            normal: Color::rgb(0.15, 0.15, 0.15),
-            hovered: Color::rgb(0.25, 0.25, 0.25),
-        }
-    }
-}
-
-#[derive(Component)]
-struct Menu;
-
-fn setup_menu(
-    mut commands: Commands,
-    textures: Res<TextureAssets>,
-    mut create_parallax: EventWriter<CreateParallaxEvent>,
-) {
+fn setup_menu(mut commands: Commands, textures: Res<TextureAssets>) {
     info!("menu");
-    let camera = commands
-        .spawn(Camera2dBundle::default())
-        // .spawn(Camera2dBundle {
-        //     transform: Transform::from_xyz(256., -144., 0.),
-        //     ..Default::default()
-        // })
-        .insert(ParallaxCameraComponent::default())
-        .id();
-
-    create_parallax.send(CreateParallaxEvent {
-        layers_data: vec![
-            LayerData {
-                speed: LayerSpeed::Bidirectional(0.9, 0.9),
-                repeat: LayerRepeat::horizontally(RepeatStrategy::Mirror),
-                path: "textures/Level07_Scene01_Sky.png".to_string(),
-                tile_size: Vec2::new(1824.0, 112.0),
-                cols: 1,
-                rows: 1,
-                z: -2.,
-                position: Vec2::new(0., 144. - (112.0 / 2.)),
-                ..default()
-            },
-            LayerData {
-                speed: LayerSpeed::Bidirectional(0.7, 0.7),
-                repeat: LayerRepeat::horizontally(RepeatStrategy::Mirror),
-                path: "textures/Level07_Scene01_Buildings02.png".to_string(),
-                tile_size: Vec2::new(2416.0, 112.0),
-                cols: 1,
-                rows: 1,
-                z: -1.5,
-                position: Vec2::new(0., 144. - (112.0 / 2.)),
-                ..default()
-            },
-            LayerData {
-                speed: LayerSpeed::Bidirectional(0.6, 0.8),
-                repeat: LayerRepeat::horizontally(RepeatStrategy::Mirror),
-                path: "textures/Level07_Scene01_Buildings01.png".to_string(),
-                tile_size: Vec2::new(2416.0, 248.0),
-                z: -0.5,
-                position: Vec2::new(0., 144. - (248.0 / 2.)),
-                ..default()
-            },
-            LayerData {
-                speed: LayerSpeed::Bidirectional(0.3, 0.4),
-                repeat: LayerRepeat::horizontally(RepeatStrategy::Mirror),
-                path: "textures/Level07_Scene01_Trees.png".to_string(),
-                tile_size: Vec2::new(168.0, 136.0),
-                cols: 1,
-                rows: 1,
-                z: -0.3,
-                position: Vec2::new(0., 0.),
-                ..default()
-            },
-        ],
-        camera,
-    });
-
     commands
         .spawn((
             NodeBundle {
@@ -112,36 +30,12 @@ fn setup_menu(
                 },
                 ..default()
             },
-            Menu,
+            DespawnOnExit(GameState::Menu),
+            MenuRoot,
         ))
         .with_children(|children| {
-            let button_colors = ButtonColors::default();
-            children
-                .spawn((
-                    ButtonBundle {
-                        style: Style {
-                            width: Val::Px(140.0),
-                            height: Val::Px(50.0),
-                            justify_content: JustifyContent::Center,
-                            align_items: AlignItems::Center,
-                            ..Default::default()
-                        },
-                        background_color: button_colors.normal.into(),
-                        ..Default::default()
-                    },
-                    button_colors,
-                    ChangeState(GameState::Playing),
-                ))
-                .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Play",
-                        TextStyle {
-                            font_size: 40.0,
-                            color: Color::rgb(0.9, 0.9, 0.9),
-                            ..default()
-                        },
-                    ));
-                });
+            spawn_button(children, "Play", 140., OpenLoadMenu);
+            spawn_button(children, "Settings", 200., OpenSettings);
         });
     commands
         .spawn((
@@ -157,7 +51,8 @@ fn setup_menu(
                 },
                 ..default()
             },
-            Menu,
+            DespawnOnExit(GameState::Menu),
+            MenuRoot,
         ))
         .with_children(|children| {
             children
@@ -214,7 +109,7 @@ fn setup_menu(
                     },
                     ButtonColors {
                         normal: Color::NONE,
-                        hovered: Color::rgb(0.25, 0.25, 0.25),
+                        ..default()
                     },
                     OpenLink("https://github.com/NiklasEi/bevy_game_template"),
                 ))
@@ -240,47 +135,39 @@ fn setup_menu(
 }
 
 #[derive(Component)]
-struct ChangeState(GameState);
+struct OpenLoadMenu;
+
+#[derive(Component)]
+struct OpenSettings;
 
 #[derive(Component)]
 struct OpenLink(&'static str);
 
 fn click_play_button(
-    mut next_state: ResMut<NextState<GameState>>,
-    mut interaction_query: Query<
+    mut next_load_state: ResMut<NextState<LoadMenu>>,
+    mut next_settings_state: ResMut<NextState<SettingsMenu>>,
+    interaction_query: Query<
         (
             &Interaction,
-            &mut BackgroundColor,
-            &ButtonColors,
-            Option<&ChangeState>,
+            Option<&OpenLoadMenu>,
+            Option<&OpenSettings>,
             Option<&OpenLink>,
         ),
         (Changed<Interaction>, With<Button>),
     >,
 ) {
-    for (interaction, mut color, button_colors, change_state, open_link) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                if let Some(state) = change_state {
-                    next_state.set(state.0.clone());
-                } else if let Some(link) = open_link {
-                    if let Err(error) = webbrowser::open(link.0) {
-                        warn!("Failed to open link {error:?}");
-                    }
-                }
-            }
-            Interaction::Hovered => {
-                *color = button_colors.hovered.into();
-            }
-            Interaction::None => {
-                *color = button_colors.normal.into();
+    for (interaction, open_load_menu, open_settings, open_link) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if open_load_menu.is_some() {
+            next_load_state.set(LoadMenu::Open);
+        } else if open_settings.is_some() {
+            next_settings_state.set(SettingsMenu::Open);
+        } else if let Some(link) = open_link {
+            if let Err(error) = webbrowser::open(link.0) {
+                warn!("Failed to open link {error:?}");
             }
         }
     }
 }
-
-fn cleanup_menu(mut commands: Commands, menu: Query<Entity, With<Menu>>) {
-    for entity in menu.iter() {
-        commands.entity(entity).despawn_recursive();
-    }
-}
@@ -0,0 +1,118 @@
+use crate::actions::Actions;
+use crate::audio::SfxEvent;
+use crate::health::Dead;
+use crate::player::dash::Dashing;
+use crate::player::wall::WallKick;
+use crate::player::{
+    one_way_platform_below, DoubleJump, Jump, Player, MAX_JUMP_HEIGHT, PLAYER_HALF_HEIGHT,
+};
+use crate::states::{DropThrough, NearLadder, OneWayPlatform};
+use bevy::prelude::*;
+use bevy_rapier2d::control::{KinematicCharacterController, KinematicCharacterControllerOutput};
+
+const CLIMB_VELOCITY: f32 = 140.0;
+/// How far below a ladder's top the player climbs off it
+const TOP_OUT_MARGIN: f32 = 4.0;
+/// Height of the hop onto the ground above a ladder
+const TOP_OUT_HOP: f32 = 30.0;
+
+/// Moving up and down a ladder without gravity
+#[derive(Component)]
+pub(super) struct Climbing;
+
+/// Up next to a ladder grabs it, down grabs it from above
+pub(super) fn start_climbing(
+    actions: Res<Actions>,
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &Transform,
+            &NearLadder,
+            &KinematicCharacterControllerOutput,
+        ),
+        (
+            With<Player>,
+            Without<Climbing>,
+            Without<Dashing>,
+            Without<Dead>,
+        ),
+    >,
+    platforms: Query<(), With<OneWayPlatform>>,
+) {
+    let Some(movement) = actions.player_movement else {
+        return;
+    };
+    for (player, transform, near_ladder, output) in &query {
+        let feet = transform.translation.y - PLAYER_HALF_HEIGHT;
+        let below_top = feet < near_ladder.ladder.top - TOP_OUT_MARGIN;
+        let mut player = commands.entity(player);
+        if movement.y > 0.5 && below_top {
+            player
+                .remove::<(Jump, DoubleJump, WallKick)>()
+                .insert(Climbing);
+        } else if movement.y < -0.5 && !output.grounded {
+            player.insert(Climbing);
+        } else if movement.y < -0.5 && !below_top {
+            // climb down through the platform at the top of the ladder
+            if let Some(platform) = one_way_platform_below(output, &platforms) {
+                player.insert((Climbing, DropThrough(platform)));
+            }
+        }
+    }
+}
+
+pub(super) fn climb(
+    actions: Res<Actions>,
+    mut commands: Commands,
+    time: Res<Time>,
+    mut sfx: EventWriter<SfxEvent>,
+    mut query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut KinematicCharacterController,
+            &KinematicCharacterControllerOutput,
+            Option<&NearLadder>,
+            Has<DropThrough>,
+        ),
+        (With<Player>, With<Climbing>),
+    >,
+) {
+    for (player, transform, mut controller, output, near_ladder, dropping_through) in &mut query {
+        let Some(near_ladder) = near_ladder else {
+            // climbed off the side of the ladder
+            commands.entity(player).remove::<Climbing>();
+            continue;
+        };
+        // up climbs, even if it is bound to jump as well
+        if actions.jump_started && !actions.jump_from_up {
+            commands
+                .entity(player)
+                .remove::<Climbing>()
+                .insert(Jump(0.0));
+            sfx.send(SfxEvent::Jump);
+            continue;
+        }
+        let vertical = actions.player_movement.map_or(0.0, |movement| movement.y);
+        let feet = transform.translation.y - PLAYER_HALF_HEIGHT;
+        if vertical > 0.0 && feet >= near_ladder.ladder.top - TOP_OUT_MARGIN {
+            // hop onto the ground above the ladder
+            commands
+                .entity(player)
+                .remove::<Climbing>()
+                .insert(Jump(MAX_JUMP_HEIGHT - TOP_OUT_HOP));
+            continue;
+        }
+        if vertical < 0.0 && output.grounded && !dropping_through {
+            // reached the bottom of the ladder
+            commands.entity(player).remove::<Climbing>();
+            continue;
+        }
+        let max_step = CLIMB_VELOCITY * time.delta_seconds();
+        controller.translation = Some(Vec2::new(
+            (near_ladder.ladder.x - transform.translation.x).clamp(-max_step, max_step),
+            vertical * max_step,
+        ));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::actions::Actions;
+use crate::audio::SfxEvent;
+use crate::health::{Dead, Invulnerable};
+use crate::player::climb::Climbing;
+use crate::player::wall::WallKick;
+use crate::player::{Abilities, Ability, AirMoves, Direction, DoubleJump, Jump, Player};
+use bevy::prelude::*;
+use bevy_rapier2d::control::{KinematicCharacterController, KinematicCharacterControllerOutput};
+
+const DASH_VELOCITY: f32 = 720.0;
+const DASH_DURATION: f32 = 0.18;
+/// Seconds after a dash started before the next one
+const DASH_COOLDOWN: f32 = 0.8;
+/// The player can not be hit during a dash and shortly after it
+const DASH_INVULNERABILITY: f32 = DASH_DURATION + 0.12;
+
+/// Moves the player horizontally at `DASH_VELOCITY`, ignoring gravity and the movement input
+#[derive(Component)]
+pub(super) struct Dashing {
+    direction: f32,
+    timer: Timer,
+}
+
+/// Time until the player can dash again
+#[derive(Component)]
+pub struct DashCooldown(pub Timer);
+
+pub(super) fn start_dash(
+    actions: Res<Actions>,
+    mut commands: Commands,
+    mut sfx: EventWriter<SfxEvent>,
+    mut query: Query<
+        (
+            Entity,
+            &KinematicCharacterControllerOutput,
+            &Abilities,
+            &Direction,
+            &mut AirMoves,
+        ),
+        (
+            With<Player>,
+            Without<DashCooldown>,
+            Without<Dashing>,
+            Without<Dead>,
+        ),
+    >,
+) {
+    if !actions.dash {
+        return;
+    }
+    for (player, output, abilities, direction, mut air_moves) in &mut query {
+        if !abilities.has(Ability::Dash) || (!output.grounded && air_moves.dashed) {
+            continue;
+        }
+        air_moves.dashed = !output.grounded;
+        let direction = match actions.player_movement {
+            Some(movement) if movement.x != 0.0 => movement.x.signum(),
+            // `update_direction` sets `Direction::Left` while moving right
+            _ => match direction {
+                Direction::Left => 1.0,
+                Direction::Right => -1.0,
+            },
+        };
+        commands
+            .entity(player)
+            .remove::<(Jump, DoubleJump, WallKick, Climbing)>()
+            .insert((
+                Dashing {
+                    direction,
+                    timer: Timer::from_seconds(DASH_DURATION, TimerMode::Once),
+                },
+                DashCooldown(Timer::from_seconds(DASH_COOLDOWN, TimerMode::Once)),
+                Invulnerable::new(DASH_INVULNERABILITY),
+            ));
+        sfx.send(SfxEvent::Dash);
+    }
+}
+
+pub(super) fn dash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut KinematicCharacterController, &mut Dashing), With<Player>>,
+) {
+    for (player, mut controller, mut dashing) in &mut query {
+        dashing.timer.tick(time.delta());
+        controller.translation = Some(Vec2::new(
+            dashing.direction * DASH_VELOCITY * time.delta_seconds(),
+            0.0,
+        ));
+        if dashing.timer.finished() {
+            commands.entity(player).remove::<Dashing>();
+        }
+    }
+}
+
+pub(super) fn tick_dash_cooldown(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut DashCooldown)>,
+) {
+    for (entity, mut cooldown) in &mut query {
+        if cooldown.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<DashCooldown>();
+        }
+    }
+}
@@ -0,0 +1,647 @@
+use crate::actions::{set_movement_actions, Actions};
+use crate::animation::{AnimationSpeed, SpriteSheetAnimation};
+use crate::audio::SfxEvent;
+use crate::character::SlopeSettings;
+use crate::cutscene::ActiveCutscene;
+use crate::despawn::DespawnOnExit;
+use crate::from_component::FromComponentPlugin;
+use crate::health::{Dead, Health};
+use crate::loading::TextureAssets;
+use crate::save::Progress;
+use crate::states::{CurrentLevel, DropThrough, InWater, NearLadder, OneWayPlatform, PLAYER_GROUP};
+use crate::{GameState, GameplayLabel};
+use bevy::prelude::*;
+use bevy_rapier2d::control::{KinematicCharacterController, KinematicCharacterControllerOutput};
+use bevy_rapier2d::dynamics::RigidBody;
+use bevy_rapier2d::geometry::{Collider, CollisionGroups, Group};
+use climb::{climb, start_climbing, Climbing};
+use dash::{dash, start_dash, tick_dash_cooldown, Dashing};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use swim::swim;
+use wall::{detect_walls, kick_off_wall, TouchingWall, WallKick, WallSliding, WALL_SLIDE_FACTOR};
+
+mod climb;
+mod dash;
+mod swim;
+mod wall;
+
+pub use dash::DashCooldown;
+
+pub const PLAYER_VELOCITY_X: f32 = 260.0;
+const PLAYER_VELOCITY_Y: f32 = 280.0;
+
+const MAX_JUMP_HEIGHT: f32 = 120.0;
+
+const PLAYER_RADIUS: f32 = 16.0;
+/// Distance from the center of the player's collider to its feet
+pub const PLAYER_HALF_HEIGHT: f32 = 18.0 + PLAYER_RADIUS;
+
+/// How much slopes slow down the running animation uphill and speed it up downhill
+const SLOPE_ANIMATION_FACTOR: f32 = 0.6;
+
+pub const MAX_HEALTH: u32 = 3;
+/// Seconds a sword swing takes, the player can not attack again before it is over
+pub const ATTACK_DURATION: f32 = 0.5;
+
+pub struct PlayerPlugin;
+
+/// Systems moving the player from its inputs
+/// Systems adding to the player's movement, like moving platforms, run after them
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct PlayerLabel;
+
+#[derive(Component)]
+pub struct Player;
+
+/// Abilities the player unlocks while progressing through the game
+/// Unlocked abilities are stored in the save game's [`Progress`]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum Ability {
+    /// Falling slower while pushing against a wall
+    WallSlide,
+    /// Jumping off a wall the player pushes against
+    WallJump,
+    /// A short burst of speed on the ground or once per jump in the air
+    Dash,
+    /// Jumping a second time in the air
+    DoubleJump,
+}
+
+/// The abilities the player has unlocked, kept in sync with the ones in [`Progress`]
+#[derive(Component, Clone, Debug, Default)]
+pub struct Abilities(BTreeSet<Ability>);
+
+impl Abilities {
+    pub fn has(&self, ability: Ability) -> bool {
+        self.0.contains(&ability)
+    }
+
+    /// Returns `false` if the ability was already unlocked
+    pub fn unlock(&mut self, ability: Ability) -> bool {
+        self.0.insert(ability)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Component, Default, Deserialize)]
+pub enum PlayerAnimationState {
+    #[default]
+    Idle,
+    Running,
+    Falling,
+    Jump,
+    Attack,
+    WallSlide,
+    DoubleJump,
+    Climb,
+    Swim,
+}
+
+impl From<PlayerAnimationState> for SpriteSheetAnimation {
+    fn from(animation_state: PlayerAnimationState) -> Self {
+        match animation_state {
+            PlayerAnimationState::Idle => SpriteSheetAnimation {
+                indices: 5..20,
+                frame_timer: Timer::from_seconds(1. / 12., TimerMode::Repeating),
+                repeat: true,
+            },
+            PlayerAnimationState::Running => SpriteSheetAnimation {
+                indices: 28..34,
+                frame_timer: Timer::from_seconds(1. / 12., TimerMode::Repeating),
+                repeat: true,
+            },
+            PlayerAnimationState::Falling => SpriteSheetAnimation {
+                indices: 20..22,
+                frame_timer: Timer::from_seconds(1. / 2., TimerMode::Repeating),
+                repeat: false,
+            },
+            PlayerAnimationState::Jump => SpriteSheetAnimation {
+                indices: 26..28,
+                frame_timer: Timer::from_seconds(1. / 2., TimerMode::Repeating),
+                repeat: false,
+            },
+            PlayerAnimationState::Attack => SpriteSheetAnimation {
+                indices: 0..5,
+                frame_timer: Timer::from_seconds(1. / 8., TimerMode::Repeating),
+                repeat: false,
+            },
+            PlayerAnimationState::WallSlide => SpriteSheetAnimation {
+                indices: 20..21,
+                frame_timer: Timer::from_seconds(1. / 2., TimerMode::Repeating),
+                repeat: false,
+            },
+            // the sprite sheet has no climbing frames yet, so climbing shows the jump frames
+            PlayerAnimationState::Climb => SpriteSheetAnimation {
+                indices: 26..28,
+                frame_timer: Timer::from_seconds(1. / 6., TimerMode::Repeating),
+                repeat: true,
+            },
+            // the sprite sheet has no swimming frames yet, so swimming shows the running frames
+            PlayerAnimationState::Swim => SpriteSheetAnimation {
+                indices: 28..34,
+                frame_timer: Timer::from_seconds(1. / 8., TimerMode::Repeating),
+                repeat: true,
+            },
+            PlayerAnimationState::DoubleJump => SpriteSheetAnimation {
+                indices: 22..26,
+                frame_timer: Timer::from_seconds(1. / 12., TimerMode::Repeating),
+                repeat: false,
+            },
+        }
+    }
+}
+
+/// This plugin handles player related stuff like movement
+/// Player logic is only active during the State `GameState::Playing`
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(FromComponentPlugin::<
+            PlayerAnimationState,
+            SpriteSheetAnimation,
+        >::new())
+            .add_systems(OnEnter(GameState::Playing), spawn_player)
+            .add_systems(
+                Update,
+                (
+                    // systems writing the controller's translation, later ones win:
+                    // running sets x, jumping and falling set y, a wall kick replaces x,
+                    // climbing replaces both, swimming slows x down and replaces y,
+                    // and a dash replaces everything
+                    (
+                        movement.run_if(not(resource_exists::<ActiveCutscene>())),
+                        rise,
+                        fall,
+                        kick_off_wall,
+                        climb,
+                        swim,
+                        dash,
+                    )
+                        .chain(),
+                    // cutscenes take control of the player
+                    (start_attack, jump, apply_movement_animation)
+                        .run_if(not(resource_exists::<ActiveCutscene>())),
+                    swing_sword,
+                    detect_walls,
+                    start_dash,
+                    tick_dash_cooldown,
+                    reset_air_moves,
+                    start_climbing,
+                    adjust_animation_speed,
+                    update_direction,
+                    update_sprite_direction,
+                    play_movement_sfx,
+                )
+                    .after(set_movement_actions)
+                    .in_set(PlayerLabel)
+                    .in_set(GameplayLabel),
+            );
+    }
+}
+
+fn spawn_player(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    level: CurrentLevel,
+    progress: Res<Progress>,
+) {
+    commands
+        .spawn(SpriteSheetBundle {
+            texture_atlas: textures.april.clone(),
+            sprite: TextureAtlasSprite::new(0),
+            transform: Transform::from_translation(level.spawn_point().extend(1.)),
+            ..Default::default()
+        })
+        .insert(Player)
+        .insert(DespawnOnExit(GameState::Playing))
+        .insert(RigidBody::KinematicPositionBased)
+        .insert(Collider::capsule_y(
+            PLAYER_HALF_HEIGHT - PLAYER_RADIUS,
+            PLAYER_RADIUS,
+        ))
+        .insert(KinematicCharacterController {
+            // lets one-way platforms decide whether they block the player
+            filter_groups: Some(CollisionGroups::new(PLAYER_GROUP, Group::ALL)),
+            ..default()
+        })
+        .insert(SlopeSettings::default())
+        .insert(Direction::Right)
+        .insert(PlayerAnimationState::Idle)
+        .insert(AnimationSpeed(1.))
+        .insert(JumpBuffer(0.1))
+        .insert(AirBuffer(0.1))
+        .insert(Grounded(false))
+        .insert(Health(progress.health.clamp(1, MAX_HEALTH)))
+        .insert(Abilities(progress.abilities.clone()))
+        .insert(AirMoves::default())
+        .insert(ActorStatus {
+            attack_timer: 0.0,
+            attacking: false,
+        });
+}
+
+fn movement(
+    actions: Res<Actions>,
+    time: Res<Time>,
+    mut query: Query<&mut KinematicCharacterController, (With<Player>, Without<Dead>)>,
+) {
+    let Ok(mut player) = query.get_single_mut() else {
+        return;
+    };
+
+    let mut movement = 0.0;
+
+    if let Some(player_movement) = actions.player_movement {
+        if player_movement.x > 0.0 {
+            movement += time.delta_seconds() * PLAYER_VELOCITY_X;
+        }
+
+        if player_movement.x < 0.0 {
+            movement += time.delta_seconds() * PLAYER_VELOCITY_X * -1.0;
+        }
+    }
+
+    match player.translation {
+        Some(vec) => player.translation = Some(Vec2::new(movement, vec.y)),
+        None => player.translation = Some(Vec2::new(movement, 0.0)),
+    }
+}
+
+#[derive(Component)]
+struct Jump(f32);
+
+/// Seconds since the current attack started
+#[derive(Component)]
+pub struct Swing(pub f32);
+
+#[derive(Component)]
+pub struct WeaponSwingAttackComponent {
+    pub attack_duration: Timer,
+}
+
+#[derive(Component)]
+pub struct ActorStatus {
+    pub attacking: bool,
+    pub attack_timer: f32,
+}
+#[derive(Component)]
+struct AirBuffer(f32);
+
+#[derive(Component)]
+struct JumpBuffer(f32);
+
+/// Whether the player touched the ground during the previous frame
+#[derive(Component)]
+struct Grounded(bool);
+
+/// Moves that can only be used once until the player lands again
+#[derive(Component, Default)]
+struct AirMoves {
+    dashed: bool,
+    double_jumped: bool,
+}
+
+/// Rising from a jump in the air
+#[derive(Component)]
+struct DoubleJump;
+
+#[derive(Component)]
+pub enum Direction {
+    Right,
+    Left,
+}
+
+fn jump(
+    actions: Res<Actions>,
+    mut commands: Commands,
+    mut sfx: EventWriter<SfxEvent>,
+    mut query: Query<
+        (
+            Entity,
+            &KinematicCharacterControllerOutput,
+            Option<&TouchingWall>,
+            &Abilities,
+            &mut AirMoves,
+            Has<NearLadder>,
+        ),
+        (
+            With<KinematicCharacterController>,
+            Without<Jump>,
+            Without<Dashing>,
+            Without<Climbing>,
+            Without<InWater>,
+            Without<DropThrough>,
+            With<Player>,
+            Without<Dead>,
+        ),
+    >,
+    platforms: Query<(), With<OneWayPlatform>>,
+) {
+    for (player, output, touching_wall, abilities, mut air_moves, near_ladder) in &mut query {
+        if near_ladder && actions.jump_from_up {
+            // up grabs the ladder instead
+            continue;
+        }
+        if !output.grounded {
+            if !actions.jump_started {
+                continue;
+            }
+            let can_wall_jump = abilities.has(Ability::WallJump);
+            if let (true, Some(wall)) = (can_wall_jump, touching_wall) {
+                commands
+                    .entity(player)
+                    .insert((Jump(0.0), WallKick::new(wall.away)));
+                sfx.send(SfxEvent::Jump);
+            } else if abilities.has(Ability::DoubleJump) && !air_moves.double_jumped {
+                air_moves.double_jumped = true;
+                commands.entity(player).insert((Jump(0.0), DoubleJump));
+                sfx.send(SfxEvent::Jump);
+            }
+            continue;
+        }
+        if !actions.jump {
+            continue;
+        }
+        let holding_down = actions
+            .player_movement
+            .is_some_and(|movement| movement.y < -0.5);
+        let platform = one_way_platform_below(output, &platforms);
+        if let (true, Some(platform)) = (holding_down, platform) {
+            // down and jump drops through the one-way platform instead of jumping
+            commands.entity(player).insert(DropThrough(platform));
+        } else {
+            commands.entity(player).insert(Jump(0.0));
+            sfx.send(SfxEvent::Jump);
+        }
+    }
+}
+
+fn start_attack(
+    actions: Res<Actions>,
+    mut commands: Commands,
+    mut sfx: EventWriter<SfxEvent>,
+    mut query: Query<
+        (
+            Entity,
+            &KinematicCharacterControllerOutput,
+            &mut ActorStatus,
+        ),
+        (
+            With<KinematicCharacterController>,
+            Without<Swing>,
+            With<Player>,
+            Without<Dead>,
+        ),
+    >,
+) {
+    for (player, output, mut status) in &mut query {
+        if actions.attack && output.grounded {
+            status.attacking = true;
+            commands.entity(player).insert(Swing(0.0));
+            sfx.send(SfxEvent::Swing);
+        }
+    }
+}
+
+fn swing_sword(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Swing, &mut ActorStatus), With<Player>>,
+) {
+    if query.is_empty() {
+        return;
+    }
+    for (entity, mut swing, mut actor_status) in query.iter_mut() {
+        let attack = time.delta().as_secs_f32();
+        if attack + swing.0 >= ATTACK_DURATION {
+            actor_status.attacking = false;
+            commands.entity(entity).remove::<Swing>();
+        }
+        swing.0 += attack;
+    }
+}
+
+fn rise(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut KinematicCharacterController, &mut Jump), With<Player>>,
+) {
+    if query.is_empty() {
+        return;
+    }
+
+    let (entity, mut player, mut jump) = query.single_mut();
+
+    let mut movement = time.delta().as_secs_f32() * 650.;
+
+    if movement + jump.0 >= MAX_JUMP_HEIGHT {
+        movement = MAX_JUMP_HEIGHT - jump.0;
+        commands.entity(entity).remove::<(Jump, DoubleJump)>();
+    }
+
+    jump.0 += movement;
+
+    match player.translation {
+        Some(vec) => player.translation = Some(Vec2::new(vec.x, movement)),
+        None => player.translation = Some(Vec2::new(0.0, movement)),
+    }
+}
+
+fn fall(
+    time: Res<Time>,
+    mut query: Query<
+        (&mut KinematicCharacterController, Has<WallSliding>),
+        (
+            Without<Jump>,
+            Without<Dashing>,
+            Without<Climbing>,
+            Without<InWater>,
+            With<Player>,
+        ),
+    >,
+) {
+    if query.is_empty() {
+        return;
+    }
+
+    let (mut player, wall_sliding) = query.single_mut();
+    let mut movement = time.delta().as_secs_f32() * (PLAYER_VELOCITY_Y / 0.95) * -1.0;
+    if wall_sliding {
+        movement *= WALL_SLIDE_FACTOR;
+    }
+
+    match player.translation {
+        Some(vec) => player.translation = Some(Vec2::new(vec.x, movement)),
+        None => player.translation = Some(Vec2::new(0.0, movement)),
+    }
+}
+
+fn apply_movement_animation(
+    mut query: Query<
+        (
+            &mut PlayerAnimationState,
+            &KinematicCharacterControllerOutput,
+            &ActorStatus,
+            Has<WallSliding>,
+            Has<DoubleJump>,
+            Has<Climbing>,
+            Has<InWater>,
+        ),
+        With<Player>,
+    >,
+) {
+    if query.is_empty() {
+        return;
+    }
+
+    for (mut animation_state, output, status, wall_sliding, double_jump, climbing, in_water) in
+        query.iter_mut()
+    {
+        if status.attacking {
+            if *animation_state != PlayerAnimationState::Attack {
+                *animation_state = PlayerAnimationState::Attack;
+            }
+        } else if climbing {
+            if *animation_state != PlayerAnimationState::Climb {
+                *animation_state = PlayerAnimationState::Climb;
+            }
+        } else if in_water {
+            if *animation_state != PlayerAnimationState::Swim {
+                *animation_state = PlayerAnimationState::Swim;
+            }
+        } else if double_jump && !output.grounded {
+            if *animation_state != PlayerAnimationState::DoubleJump {
+                *animation_state = PlayerAnimationState::DoubleJump;
+            }
+        } else if wall_sliding && output.desired_translation.y < 0.0 && !output.grounded {
+            if *animation_state != PlayerAnimationState::WallSlide {
+                *animation_state = PlayerAnimationState::WallSlide;
+            }
+        } else if output.desired_translation.x != 0.0 && output.grounded {
+            if *animation_state != PlayerAnimationState::Running {
+                *animation_state = PlayerAnimationState::Running;
+            }
+        } else if output.desired_translation.x == 0.0 && output.grounded {
+            if *animation_state != PlayerAnimationState::Idle {
+                *animation_state = PlayerAnimationState::Idle;
+            }
+        } else if output.desired_translation.y > 0.0 && !output.grounded {
+            if *animation_state != PlayerAnimationState::Jump {
+                *animation_state = PlayerAnimationState::Jump;
+            }
+        } else if output.desired_translation.y < 0.0 && !output.grounded {
+            if *animation_state != PlayerAnimationState::Falling {
+                *animation_state = PlayerAnimationState::Falling;
+            }
+        }
+    }
+}
+
+/// Landing, grabbing a wall or climbing allows dashing and double jumping in the air again
+fn reset_air_moves(
+    mut query: Query<
+        (
+            &KinematicCharacterControllerOutput,
+            &mut AirMoves,
+            Has<TouchingWall>,
+            Has<Climbing>,
+        ),
+        With<Player>,
+    >,
+) {
+    for (output, mut air_moves, touching_wall, climbing) in &mut query {
+        let holding_on = output.grounded || touching_wall || climbing;
+        if holding_on && (air_moves.dashed || air_moves.double_jumped) {
+            *air_moves = AirMoves::default();
+        }
+    }
+}
+
+/// The one-way platform the player stands on, if any
+fn one_way_platform_below(
+    output: &KinematicCharacterControllerOutput,
+    platforms: &Query<(), With<OneWayPlatform>>,
+) -> Option<Entity> {
+    output
+        .collisions
+        .iter()
+        .map(|collision| collision.entity)
+        .find(|entity| platforms.contains(*entity))
+}
+
+/// Running uphill plays the running animation slower, running downhill faster
+/// The climbing animation only plays while the player moves along the ladder
+fn adjust_animation_speed(
+    mut query: Query<
+        (
+            &KinematicCharacterControllerOutput,
+            &PlayerAnimationState,
+            &mut AnimationSpeed,
+        ),
+        With<Player>,
+    >,
+) {
+    for (output, animation_state, mut speed) in &mut query {
+        // level geometry is never rotated, so the ground's local normal is its world normal
+        let ground_normal = output
+            .collisions
+            .iter()
+            .map(|collision| collision.toi.normal1)
+            .find(|normal| normal.y > 0.);
+        let target = match (animation_state, ground_normal) {
+            (PlayerAnimationState::Running, Some(normal)) => {
+                // the sine of the slope's angle, negative when running uphill
+                let downhill = normal.x * output.desired_translation.x.signum();
+                1. + SLOPE_ANIMATION_FACTOR * downhill
+            }
+            (PlayerAnimationState::Climb, _) if output.desired_translation.y == 0. => 0.,
+            _ => 1.,
+        };
+        if speed.0 != target {
+            speed.0 = target;
+        }
+    }
+}
+
+fn play_movement_sfx(
+    mut sfx: EventWriter<SfxEvent>,
+    mut query: Query<(&KinematicCharacterControllerOutput, &mut Grounded), With<Player>>,
+) {
+    for (output, mut grounded) in &mut query {
+        if output.grounded && !grounded.0 {
+            sfx.send(SfxEvent::Land);
+        } else if output.grounded && output.desired_translation.x != 0.0 {
+            // footsteps are requested every frame, the sound's cooldown sets the pace
+            sfx.send(SfxEvent::Footstep);
+        }
+        grounded.0 = output.grounded;
+    }
+}
+
+fn update_direction(
+    mut commands: Commands,
+    query: Query<(Entity, &KinematicCharacterControllerOutput), With<Player>>,
+) {
+    if query.is_empty() {
+        return;
+    }
+
+    for (player, output) in query.iter() {
+        if output.desired_translation.x > 0.0 {
+            commands.entity(player).insert(Direction::Left);
+        } else if output.desired_translation.x < 0.0 {
+            commands.entity(player).insert(Direction::Right);
+        }
+    }
+}
+fn update_sprite_direction(mut query: Query<(&mut TextureAtlasSprite, &Direction), With<Player>>) {
+    if query.is_empty() {
+        return;
+    }
+
+    for (mut sprite, direction) in &mut query {
+        match direction {
+            Direction::Right => sprite.flip_x = true,
+            Direction::Left => sprite.flip_x = false,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::actions::Actions;
+use crate::audio::SfxEvent;
+use crate::health::Dead;
+use crate::player::climb::Climbing;
+use crate::player::dash::Dashing;
+use crate::player::{Jump, Player, PLAYER_HALF_HEIGHT};
+use crate::states::InWater;
+use bevy::prelude::*;
+use bevy_rapier2d::control::KinematicCharacterController;
+
+/// Horizontal speed in water, relative to running
+const SWIM_SPEED_FACTOR: f32 = 0.5;
+const SWIM_VELOCITY: f32 = 120.0;
+/// Vertical velocity while not swimming up or down, the player floats up to the surface
+const BUOYANCY: f32 = 40.0;
+/// How deep the center of the player floats below the surface, keeping the head above water
+const FLOAT_DEPTH: f32 = PLAYER_HALF_HEIGHT * 0.5;
+
+/// Replaces running, jumping and falling while the player is in water
+pub(super) fn swim(
+    actions: Res<Actions>,
+    mut commands: Commands,
+    time: Res<Time>,
+    mut sfx: EventWriter<SfxEvent>,
+    mut query: Query<
+        (
+            Entity,
+            &Transform,
+            &InWater,
+            &mut KinematicCharacterController,
+        ),
+        (
+            With<Player>,
+            Without<Jump>,
+            Without<Dashing>,
+            Without<Climbing>,
+            Without<Dead>,
+        ),
+    >,
+) {
+    for (player, transform, in_water, mut controller) in &mut query {
+        let depth = in_water.water.surface - transform.translation.y;
+        let at_surface = depth <= FLOAT_DEPTH;
+        if actions.jump_started && at_surface {
+            // jump out of the water
+            commands.entity(player).insert(Jump(0.0));
+            sfx.send(SfxEvent::Jump);
+            continue;
+        }
+        let input = actions.player_movement.unwrap_or_default();
+        let mut vertical = if input.y < -0.5 {
+            -SWIM_VELOCITY
+        } else if input.y > 0.5 || actions.jump {
+            SWIM_VELOCITY
+        } else {
+            BUOYANCY
+        };
+        if at_surface {
+            vertical = vertical.min(0.0);
+        }
+        let horizontal = controller.translation.map_or(0.0, |vec| vec.x);
+        controller.translation = Some(Vec2::new(
+            horizontal * SWIM_SPEED_FACTOR,
+            vertical * time.delta_seconds(),
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::player::{Abilities, Ability, Player};
+use bevy::prelude::*;
+use bevy_rapier2d::control::{KinematicCharacterController, KinematicCharacterControllerOutput};
+
+/// Fall speed while sliding down a wall, relative to falling freely
+pub(super) const WALL_SLIDE_FACTOR: f32 = 0.35;
+/// Horizontal speed of the kick away from a wall, it fades out over `WALL_KICK_DURATION`
+const WALL_KICK_VELOCITY: f32 = 420.0;
+const WALL_KICK_DURATION: f32 = 0.25;
+
+/// The player pushes against a wall while in the air
+#[derive(Component, PartialEq)]
+pub(super) struct TouchingWall {
+    /// Horizontal direction from the wall to the player, -1 or 1
+    pub(super) away: f32,
+}
+
+/// Touching a wall with [`Ability::WallSlide`] unlocked
+#[derive(Component)]
+pub(super) struct WallSliding;
+
+/// Pushes the player away from the wall after a wall jump, overriding the movement input
+#[derive(Component)]
+pub(super) struct WallKick {
+    away: f32,
+    timer: Timer,
+}
+
+impl WallKick {
+    pub(super) fn new(away: f32) -> Self {
+        WallKick {
+            away,
+            timer: Timer::from_seconds(WALL_KICK_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+/// Walls are hit while pushing against them in the air
+pub(super) fn detect_walls(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &KinematicCharacterControllerOutput,
+            Option<&TouchingWall>,
+            &Abilities,
+        ),
+        With<Player>,
+    >,
+) {
+    for (player, output, touching_wall, abilities) in &query {
+        let wall = output
+            .collisions
+            .iter()
+            .map(|collision| collision.toi.normal1)
+            // steep enough that the player can not stand on it
+            .find(|normal| normal.x.abs() > 0.9)
+            .filter(|_| !output.grounded)
+            .map(|normal| TouchingWall {
+                away: normal.x.signum(),
+            });
+        match wall {
+            Some(wall) if touching_wall != Some(&wall) => {
+                let mut player = commands.entity(player);
+                player.insert(wall);
+                if abilities.has(Ability::WallSlide) {
+                    player.insert(WallSliding);
+                }
+            }
+            None if touching_wall.is_some() => {
+                commands
+                    .entity(player)
+                    .remove::<(TouchingWall, WallSliding)>();
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(super) fn kick_off_wall(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut KinematicCharacterController, &mut WallKick), With<Player>>,
+) {
+    for (player, mut controller, mut kick) in &mut query {
+        kick.timer.tick(time.delta());
+        let movement =
+            kick.away * WALL_KICK_VELOCITY * kick.timer.percent_left() * time.delta_seconds();
+        match controller.translation {
+            Some(vec) => controller.translation = Some(Vec2::new(movement, vec.y)),
+            None => controller.translation = Some(Vec2::new(movement, 0.0)),
+        }
+        if kick.timer.finished() {
+            commands.entity(player).remove::<WallKick>();
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use crate::player::{Ability, MAX_HEALTH};
+use crate::states::{LevelId, START_LIVES};
+use crate::storage::{Location, Storage};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Bumped whenever the save format changes in a way `#[serde(default)]` can not cover
+const SAVE_VERSION: u32 = 1;
+pub const SAVE_SLOTS: usize = 3;
+
+pub struct SavePlugin;
+
+/// This plugin keeps track of the player's [`Progress`] and writes it to the active save slot
+/// Saving is requested with a [`SaveGame`] event, e.g. when a checkpoint is reached
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SaveStorage(Storage::open(Location::Saves)))
+            .init_resource::<Progress>()
+            .init_resource::<ActiveSlot>()
+            .add_event::<SaveGame>()
+            .add_systems(Update, write_save.run_if(on_event::<SaveGame>()));
+    }
+}
+
+/// Everything about a play through that survives quitting the game
+#[derive(Clone, Debug, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    pub level: LevelId,
+    /// Last checkpoint reached in `level`, the player starts at the beginning of the level if `None`
+    pub checkpoint: Option<u32>,
+    pub health: u32,
+    /// Only counted down if lives are enabled in the gameplay settings
+    pub lives: u32,
+    /// Ids of the items picked up so far, per level
+    pub collected: BTreeMap<LevelId, BTreeSet<u32>>,
+    /// Ids of the doors switched away from their state in the level data, per level
+    pub toggled_doors: BTreeMap<LevelId, BTreeSet<u32>>,
+    pub abilities: BTreeSet<Ability>,
+    /// Story flags set by dialogue, e.g. after talking to someone
+    pub flags: BTreeSet<String>,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Progress {
+            level: LevelId::default(),
+            checkpoint: None,
+            health: MAX_HEALTH,
+            lives: START_LIVES,
+            collected: BTreeMap::new(),
+            toggled_doors: BTreeMap::new(),
+            abilities: BTreeSet::new(),
+            flags: BTreeSet::new(),
+        }
+    }
+}
+
+/// The save slot progress is written to, `None` until a slot was picked in the menu
+#[derive(Default, Resource)]
+pub struct ActiveSlot(pub Option<usize>);
+
+/// Writes the current [`Progress`] to the active save slot
+#[derive(Event)]
+pub struct SaveGame;
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub progress: Progress,
+}
+
+/// Where the save slots are kept
+#[derive(Resource)]
+pub struct SaveStorage(Storage);
+
+impl SaveStorage {
+    fn key(slot: usize) -> String {
+        format!("slot{}", slot + 1)
+    }
+
+    /// Reads the save in `slot`, returns `None` for empty slots and unreadable saves
+    pub fn read_slot(&self, slot: usize) -> Option<SaveFile> {
+        let save: SaveFile = self.0.load(&SaveStorage::key(slot))?;
+        if save.version > SAVE_VERSION {
+            warn!(
+                "Save slot {} was written by a newer version of the game (version {})",
+                slot + 1,
+                save.version
+            );
+            return None;
+        }
+        Some(save)
+    }
+
+    fn write_slot(&self, slot: usize, progress: &Progress) {
+        let save = SaveFile {
+            version: SAVE_VERSION,
+            progress: progress.clone(),
+        };
+        self.0.store(&SaveStorage::key(slot), &save);
+    }
+}
+
+fn write_save(
+    mut save_game: EventReader<SaveGame>,
+    storage: Res<SaveStorage>,
+    progress: Res<Progress>,
+    active_slot: Res<ActiveSlot>,
+) {
+    // several requests in one frame result in a single write
+    save_game.clear();
+    if let Some(slot) = active_slot.0 {
+        storage.write_slot(slot, &progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{StorageBackend, StorageError};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryStorage(Mutex<BTreeMap<String, String>>);
+
+    impl StorageBackend for MemoryStorage {
+        fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+            Ok(self.0.lock().unwrap().get(key).cloned())
+        }
+
+        fn write(&self, key: &str, contents: &str) -> Result<(), StorageError> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), contents.to_string());
+            Ok(())
+        }
+    }
+
+    fn storage_with_save(version: u32) -> SaveStorage {
+        let storage = SaveStorage(Storage::new(MemoryStorage::default()));
+        let progress = Progress {
+            checkpoint: Some(2),
+            ..default()
+        };
+        storage
+            .0
+            .store(&SaveStorage::key(0), &SaveFile { version, progress });
+        storage
+    }
+
+    #[test]
+    fn empty_slot_reads_nothing() {
+        let storage = SaveStorage(Storage::new(MemoryStorage::default()));
+
+        assert!(storage.read_slot(0).is_none());
+    }
+
+    #[test]
+    fn written_slot_reads_back() {
+        let storage = SaveStorage(Storage::new(MemoryStorage::default()));
+        let progress = Progress {
+            checkpoint: Some(1),
+            ..default()
+        };
+
+        storage.write_slot(1, &progress);
+
+        let save = storage.read_slot(1).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.progress.checkpoint, Some(1));
+        assert!(storage.read_slot(0).is_none());
+    }
+
+    #[test]
+    fn older_saves_are_read() {
+        let storage = storage_with_save(SAVE_VERSION - 1);
+
+        assert_eq!(storage.read_slot(0).unwrap().progress.checkpoint, Some(2));
+    }
+
+    #[test]
+    fn saves_from_newer_versions_are_ignored() {
+        let storage = storage_with_save(SAVE_VERSION + 1);
+
+        assert!(storage.read_slot(0).is_none());
+    }
+}
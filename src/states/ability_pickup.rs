@@ -0,0 +1,88 @@
+use crate::audio::{AudioEmitter, EmitterSfxEvent, SfxEvent};
+use crate::despawn::DespawnOnExit;
+use crate::player::{Abilities, Ability, Player};
+use crate::save::Progress;
+use crate::states::berry::PickedUp;
+use crate::states::{player_sensor, sensor_and_player, CurrentLevel};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::f32::consts::FRAC_PI_4;
+
+const PICKUP_SIZE: Vec2 = Vec2::new(18., 18.);
+
+/// Unlocks an ability when the player touches it
+#[derive(Component)]
+pub(super) struct AbilityPickup(Ability);
+
+fn pickup_color(ability: Ability) -> Color {
+    match ability {
+        Ability::WallSlide => Color::rgb(0.4, 0.8, 0.4),
+        Ability::WallJump => Color::rgb(0.3, 0.6, 1.),
+        Ability::Dash => Color::rgb(1., 0.6, 0.2),
+        Ability::DoubleJump => Color::rgb(0.8, 0.4, 1.),
+    }
+}
+
+pub(super) fn spawn_ability_pickups(
+    mut commands: Commands,
+    level: CurrentLevel,
+    progress: Res<Progress>,
+) {
+    for pickup in &level.data().abilities {
+        if progress.abilities.contains(&pickup.ability) {
+            continue;
+        }
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: pickup_color(pickup.ability),
+                    custom_size: Some(PICKUP_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(pickup.position.extend(0.5))
+                    .with_rotation(Quat::from_rotation_z(FRAC_PI_4)),
+                ..default()
+            },
+            AbilityPickup(pickup.ability),
+            Collider::cuboid(PICKUP_SIZE.x * 0.5, PICKUP_SIZE.y * 0.5),
+            player_sensor(),
+            AudioEmitter::new(150., 600.),
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+pub(super) fn pick_up_abilities(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionEvent>,
+    mut sfx: EventWriter<EmitterSfxEvent>,
+    mut progress: ResMut<Progress>,
+    pickups: Query<&AbilityPickup, Without<PickedUp>>,
+    mut players: Query<&mut Abilities, With<Player>>,
+) {
+    for collision in collisions.read() {
+        let Some((pickup, player, true)) = sensor_and_player(collision, &pickups) else {
+            continue;
+        };
+        let Ok(&AbilityPickup(ability)) = pickups.get(pickup) else {
+            continue;
+        };
+        let Ok(mut abilities) = players.get_mut(player) else {
+            continue;
+        };
+
+        if abilities.unlock(ability) {
+            info!("Unlocked {ability:?}");
+        }
+        progress.abilities.insert(ability);
+        sfx.send(EmitterSfxEvent {
+            sfx: SfxEvent::Pickup,
+            emitter: pickup,
+        });
+        commands
+            .entity(pickup)
+            .remove::<Collider>()
+            .insert(PickedUp::new());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::audio::{AudioEmitter, EmitterSfxEvent, SfxEvent};
+use crate::despawn::DespawnOnExit;
+use crate::loading::TextureAssets;
+use crate::player::Player;
+use crate::save::Progress;
+use crate::states::chest::in_chest;
+use crate::states::{player_sensor, sensor_and_player, CurrentLevel};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const BERRY_SIZE: Vec2 = Vec2::new(16., 16.);
+const PICKUP_DURATION: f32 = 0.3;
+/// How far a berry floats up while it vanishes
+const PICKUP_RISE: f32 = 24.;
+
+/// Berries of the current level, shown as "collected X of Y"
+#[derive(Default, Resource)]
+pub struct BerryCount {
+    pub collected: usize,
+    pub total: usize,
+}
+
+#[derive(Component)]
+pub struct Berry(pub u32);
+
+/// A berry or other pickup that was picked up and is playing its pickup animation
+#[derive(Component)]
+pub(super) struct PickedUp(Timer);
+
+impl PickedUp {
+    pub(super) fn new() -> Self {
+        PickedUp(Timer::from_seconds(PICKUP_DURATION, TimerMode::Once))
+    }
+}
+
+pub(super) fn spawn_berries(
+    mut commands: Commands,
+    textures: Res<TextureAssets>,
+    level: CurrentLevel,
+    progress: Res<Progress>,
+    mut berry_count: ResMut<BerryCount>,
+) {
+    let berries = &level.data().berries;
+    let collected = progress.collected.get(&level.id());
+    *berry_count = BerryCount {
+        collected: collected.map_or(0, |collected| collected.len()),
+        total: berries.len(),
+    };
+
+    for (id, position) in berries.iter().enumerate() {
+        let id = id as u32;
+        if collected.is_some_and(|collected| collected.contains(&id)) || in_chest(&level, id) {
+            continue;
+        }
+        spawn_berry(&mut commands, &textures, id, *position);
+    }
+}
+
+pub(super) fn spawn_berry(
+    commands: &mut Commands,
+    textures: &TextureAssets,
+    id: u32,
+    position: Vec2,
+) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(BERRY_SIZE),
+                ..default()
+            },
+            texture: textures.berry.clone(),
+            transform: Transform::from_translation(position.extend(0.5)),
+            ..default()
+        },
+        Berry(id),
+        Collider::ball(BERRY_SIZE.x * 0.5),
+        player_sensor(),
+        AudioEmitter::new(150., 600.),
+        DespawnOnExit(GameState::Playing),
+    ));
+}
+
+pub(super) fn pick_up_berries(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionEvent>,
+    mut sfx: EventWriter<EmitterSfxEvent>,
+    mut progress: ResMut<Progress>,
+    mut berry_count: ResMut<BerryCount>,
+    berries: Query<&Berry, Without<PickedUp>>,
+    players: Query<(), With<Player>>,
+) {
+    for collision in collisions.read() {
+        let Some((berry, player, true)) = sensor_and_player(collision, &berries) else {
+            continue;
+        };
+        let Ok(&Berry(id)) = berries.get(berry) else {
+            continue;
+        };
+        if !players.contains(player) {
+            continue;
+        }
+
+        let level = progress.level;
+        if progress.collected.entry(level).or_default().insert(id) {
+            berry_count.collected += 1;
+        }
+        sfx.send(EmitterSfxEvent {
+            sfx: SfxEvent::Pickup,
+            emitter: berry,
+        });
+        commands
+            .entity(berry)
+            .remove::<Collider>()
+            .insert(PickedUp::new());
+    }
+}
+
+/// Lets picked up berries and other pickups float up and fade out before they are despawned
+pub(super) fn animate_picked_berries(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut berries: Query<(Entity, &mut PickedUp, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut picked_up, mut transform, mut sprite) in &mut berries {
+        picked_up.0.tick(time.delta());
+        let progress = picked_up.0.percent();
+        transform.translation.y += PICKUP_RISE / PICKUP_DURATION * time.delta_seconds();
+        transform.scale = Vec3::splat(1. + 0.5 * progress);
+        sprite.color.set_a(1. - progress);
+        if picked_up.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
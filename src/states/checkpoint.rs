@@ -0,0 +1,80 @@
+use crate::despawn::DespawnOnExit;
+use crate::health::Health;
+use crate::player::Player;
+use crate::save::{Progress, SaveGame};
+use crate::states::{player_sensor, sensor_and_player, CurrentLevel};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const CHECKPOINT_SIZE: Vec2 = Vec2::new(16., 96.);
+
+const INACTIVE_COLOR: Color = Color::rgba(0.9, 0.9, 0.9, 0.4);
+const ACTIVE_COLOR: Color = Color::rgba(1., 0.8, 0.2, 0.8);
+
+/// Reaching a checkpoint saves the game, after loading the player starts at the last one reached
+#[derive(Component)]
+pub struct Checkpoint(pub u32);
+
+pub(super) fn spawn_checkpoints(
+    mut commands: Commands,
+    level: CurrentLevel,
+    progress: Res<Progress>,
+) {
+    for (id, position) in level.data().checkpoints.iter().enumerate() {
+        let id = id as u32;
+        let color = if progress.checkpoint == Some(id) {
+            ACTIVE_COLOR
+        } else {
+            INACTIVE_COLOR
+        };
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(CHECKPOINT_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(0.5)),
+                ..default()
+            },
+            Checkpoint(id),
+            Collider::cuboid(CHECKPOINT_SIZE.x * 0.5, CHECKPOINT_SIZE.y * 0.5),
+            player_sensor(),
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+pub(super) fn reach_checkpoint(
+    mut collisions: EventReader<CollisionEvent>,
+    mut save_game: EventWriter<SaveGame>,
+    mut progress: ResMut<Progress>,
+    mut checkpoints: Query<(&Checkpoint, &mut Sprite)>,
+    players: Query<&Health, With<Player>>,
+) {
+    for collision in collisions.read() {
+        let Some((checkpoint, player, true)) = sensor_and_player(collision, &checkpoints) else {
+            continue;
+        };
+        let Ok(health) = players.get(player) else {
+            continue;
+        };
+        let Ok((&Checkpoint(id), _)) = checkpoints.get(checkpoint) else {
+            continue;
+        };
+        if progress.checkpoint == Some(id) {
+            continue;
+        }
+        progress.checkpoint = Some(id);
+        progress.health = health.0;
+        for (checkpoint, mut sprite) in &mut checkpoints {
+            sprite.color = if progress.checkpoint == Some(checkpoint.0) {
+                ACTIVE_COLOR
+            } else {
+                INACTIVE_COLOR
+            };
+        }
+        save_game.send(SaveGame);
+    }
+}
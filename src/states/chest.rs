@@ -0,0 +1,90 @@
+use crate::despawn::DespawnOnExit;
+use crate::loading::TextureAssets;
+use crate::save::Progress;
+use crate::states::berry::spawn_berry;
+use crate::states::interact::{Interactable, Interacted};
+use crate::states::CurrentLevel;
+use crate::GameState;
+use bevy::prelude::*;
+
+const CHEST_SIZE: Vec2 = Vec2::new(28., 20.);
+const CHEST_COLOR: Color = Color::rgb(0.6, 0.4, 0.15);
+const OPEN_CHEST_COLOR: Color = Color::rgb(0.35, 0.25, 0.1);
+/// Horizontal distance between the berries dropped by a chest
+const DROP_SPACING: f32 = 20.;
+/// Height above the chest the dropped berries appear at
+const DROP_HEIGHT: f32 = 24.;
+
+/// Drops the berries with these ids when opened
+#[derive(Component)]
+pub(super) struct Chest(Vec<u32>);
+
+/// Berries inside chests are only spawned once their chest is opened
+pub(super) fn in_chest(level: &CurrentLevel, id: u32) -> bool {
+    level
+        .data()
+        .chests
+        .iter()
+        .any(|chest| chest.berries.contains(&id))
+}
+
+pub(super) fn spawn_chests(mut commands: Commands, level: CurrentLevel, progress: Res<Progress>) {
+    let collected = progress.collected.get(&level.id());
+    for chest in &level.data().chests {
+        // chests whose berries were all collected stay open
+        let open = chest
+            .berries
+            .iter()
+            .all(|id| collected.is_some_and(|collected| collected.contains(id)));
+        let mut entity = commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: if open { OPEN_CHEST_COLOR } else { CHEST_COLOR },
+                    custom_size: Some(CHEST_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(chest.position.extend(0.4)),
+                ..default()
+            },
+            Chest(chest.berries.clone()),
+            DespawnOnExit(GameState::Playing),
+        ));
+        if !open {
+            entity.insert(Interactable);
+        }
+    }
+}
+
+pub(super) fn open_chests(
+    mut commands: Commands,
+    mut interacted: EventReader<Interacted>,
+    textures: Res<TextureAssets>,
+    progress: Res<Progress>,
+    mut chests: Query<(&Chest, &Transform, &mut Sprite)>,
+) {
+    let collected = progress.collected.get(&progress.level);
+    for &Interacted(entity) in interacted.read() {
+        let Ok((chest, transform, mut sprite)) = chests.get_mut(entity) else {
+            continue;
+        };
+        sprite.color = OPEN_CHEST_COLOR;
+        commands.entity(entity).remove::<Interactable>();
+
+        let berries: Vec<u32> = chest
+            .0
+            .iter()
+            .copied()
+            .filter(|id| !collected.is_some_and(|collected| collected.contains(id)))
+            .collect();
+        let first = -(berries.len() as f32 - 1.) * 0.5 * DROP_SPACING;
+        for (index, id) in berries.into_iter().enumerate() {
+            let offset = Vec2::new(first + index as f32 * DROP_SPACING, DROP_HEIGHT);
+            spawn_berry(
+                &mut commands,
+                &textures,
+                id,
+                transform.translation.truncate() + offset,
+            );
+        }
+    }
+}
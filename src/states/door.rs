@@ -0,0 +1,127 @@
+use crate::despawn::DespawnOnExit;
+use crate::save::Progress;
+use crate::states::interact::{Interactable, Interacted};
+use crate::states::CurrentLevel;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::collections::BTreeSet;
+
+const DOOR_COLOR: Color = Color::rgb(0.45, 0.3, 0.2);
+const OPEN_DOOR_COLOR: Color = Color::rgba(0.45, 0.3, 0.2, 0.2);
+const SWITCH_SIZE: Vec2 = Vec2::new(12., 24.);
+const SWITCH_OFF_COLOR: Color = Color::rgb(0.8, 0.2, 0.2);
+const SWITCH_ON_COLOR: Color = Color::rgb(0.2, 0.8, 0.2);
+
+/// Blocks the way while closed
+#[derive(Component)]
+pub(super) struct Door {
+    id: u32,
+    open: bool,
+}
+
+/// Opens or closes all doors with the given ids when the player interacts with it
+/// The doors' state is kept in the player's progress, so it survives respawns and saves
+#[derive(Component)]
+pub(super) struct Switch {
+    doors: Vec<u32>,
+    on: bool,
+}
+
+fn door_color(open: bool) -> Color {
+    if open {
+        OPEN_DOOR_COLOR
+    } else {
+        DOOR_COLOR
+    }
+}
+
+fn switch_color(on: bool) -> Color {
+    if on {
+        SWITCH_ON_COLOR
+    } else {
+        SWITCH_OFF_COLOR
+    }
+}
+
+pub(super) fn spawn_doors(mut commands: Commands, level: CurrentLevel, progress: Res<Progress>) {
+    let toggled = progress.toggled_doors.get(&level.id());
+    let is_toggled = |id: &u32| toggled.is_some_and(|toggled| toggled.contains(id));
+    for door in &level.data().doors {
+        let size = door.area.size();
+        let open = door.open != is_toggled(&door.id);
+        let mut entity = commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: door_color(open),
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(door.area.center().extend(0.6)),
+                ..default()
+            },
+            Door { id: door.id, open },
+            RigidBody::Fixed,
+            Collider::cuboid(size.x * 0.5, size.y * 0.5),
+            DespawnOnExit(GameState::Playing),
+        ));
+        if open {
+            entity.insert(ColliderDisabled);
+        }
+    }
+    for switch in &level.data().switches {
+        let on = switch.doors.iter().any(is_toggled);
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: switch_color(on),
+                    custom_size: Some(SWITCH_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(switch.position.extend(0.6)),
+                ..default()
+            },
+            Switch {
+                doors: switch.doors.clone(),
+                on,
+            },
+            Interactable,
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+pub(super) fn toggle_switches(
+    mut commands: Commands,
+    mut interacted: EventReader<Interacted>,
+    mut progress: ResMut<Progress>,
+    mut switches: Query<(&mut Switch, &mut Sprite), Without<Door>>,
+    mut doors: Query<(Entity, &mut Door, &mut Sprite)>,
+) {
+    for &Interacted(entity) in interacted.read() {
+        let Ok((mut switch, mut sprite)) = switches.get_mut(entity) else {
+            continue;
+        };
+        switch.on = !switch.on;
+        sprite.color = switch_color(switch.on);
+        let level = progress.level;
+        let toggled = progress.toggled_doors.entry(level).or_default();
+        for id in switch.doors.iter().collect::<BTreeSet<_>>() {
+            if !toggled.remove(id) {
+                toggled.insert(*id);
+            }
+        }
+        for (door_entity, mut door, mut sprite) in &mut doors {
+            if !switch.doors.contains(&door.id) {
+                continue;
+            }
+            door.open = !door.open;
+            sprite.color = door_color(door.open);
+            if door.open {
+                commands.entity(door_entity).insert(ColliderDisabled);
+            } else {
+                commands.entity(door_entity).remove::<ColliderDisabled>();
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::audio::{AudioEmitter, EmitterSfxEvent, SfxEvent};
+use crate::despawn::DespawnOnExit;
+use crate::states::hazard::Hazard;
+use crate::states::level_data::{EnemyData, HazardDamage};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const ENEMY_SIZE: Vec2 = Vec2::new(24., 24.);
+const ENEMY_COLOR: Color = Color::rgb(0.7, 0.2, 0.3);
+/// Seconds between two footsteps of a walking enemy
+const STEP_INTERVAL: f32 = 0.4;
+
+/// Walks back and forth between `left` and `right`, hurting the player on contact
+#[derive(Component)]
+pub(super) struct Enemy {
+    left: f32,
+    right: f32,
+    /// Pixels per second, negative while walking left
+    velocity: f32,
+    steps: Timer,
+}
+
+pub(super) fn spawn_enemy(commands: &mut Commands, enemy: &EnemyData) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: ENEMY_COLOR,
+                custom_size: Some(ENEMY_SIZE),
+                ..default()
+            },
+            transform: Transform::from_translation(enemy.position.extend(0.6)),
+            ..default()
+        },
+        Enemy {
+            left: enemy.position.x - enemy.patrol,
+            right: enemy.position.x + enemy.patrol,
+            velocity: enemy.speed,
+            steps: Timer::from_seconds(STEP_INTERVAL, TimerMode::Repeating),
+        },
+        Hazard::new(HazardDamage::Hit(1)),
+        RigidBody::KinematicPositionBased,
+        Collider::cuboid(ENEMY_SIZE.x * 0.5, ENEMY_SIZE.y * 0.5),
+        Sensor,
+        // the player is kinematic as well
+        ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_KINEMATIC,
+        AudioEmitter::new(150., 600.),
+        DespawnOnExit(GameState::Playing),
+    ));
+}
+
+pub(super) fn patrol(
+    time: Res<Time>,
+    mut sfx: EventWriter<EmitterSfxEvent>,
+    mut enemies: Query<(Entity, &mut Enemy, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut enemy, mut transform, mut sprite) in &mut enemies {
+        transform.translation.x += enemy.velocity * time.delta_seconds();
+        if transform.translation.x <= enemy.left {
+            transform.translation.x = enemy.left;
+            enemy.velocity = enemy.velocity.abs();
+        } else if transform.translation.x >= enemy.right {
+            transform.translation.x = enemy.right;
+            enemy.velocity = -enemy.velocity.abs();
+        }
+        sprite.flip_x = enemy.velocity < 0.;
+        if enemy.steps.tick(time.delta()).just_finished() {
+            sfx.send(EmitterSfxEvent {
+                sfx: SfxEvent::Footstep,
+                emitter: entity,
+            });
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use crate::audio::{AudioEmitter, EmitterSfxEvent, SfxEvent};
+use crate::despawn::DespawnOnExit;
+use crate::health::Damage;
+use crate::player::Player;
+use crate::states::level_data::{HazardDamage, HazardKind};
+use crate::states::CurrentLevel;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const SPIKES_COLOR: Color = Color::rgb(0.75, 0.75, 0.8);
+const BLOCK_COLOR: Color = Color::rgb(0.35, 0.33, 0.38);
+/// Height of retracted spikes, relative to extended ones
+const RETRACTED_SCALE: f32 = 0.2;
+const FALL_ACCELERATION: f32 = 1800.;
+/// How far the player may be below a falling block to trigger it
+const FALL_TRIGGER_DISTANCE: f32 = 400.;
+/// How far a triggered block shakes back and forth before it falls
+const SHAKE_AMPLITUDE: f32 = 2.;
+/// Height of the sensor along the bottom of falling blocks and crushers that hurts the player
+const CRUSHING_EDGE_HEIGHT: f32 = 6.;
+/// Shortest extended or retracted phase of retracting spikes, in seconds
+const MIN_SPIKE_PHASE: f32 = 0.1;
+const CRUSHER_SLAM_VELOCITY: f32 = 900.;
+const CRUSHER_RISE_VELOCITY: f32 = 120.;
+
+/// Hurts the player as long as they touch it
+#[derive(Component)]
+pub(super) struct Hazard {
+    damage: HazardDamage,
+    active: bool,
+}
+
+impl Hazard {
+    pub(super) fn new(damage: HazardDamage) -> Self {
+        Hazard {
+            damage,
+            active: true,
+        }
+    }
+}
+
+/// Spikes going in and out of the ground
+#[derive(Component)]
+pub(super) struct RetractingSpikes {
+    extended: f32,
+    retracted: f32,
+    /// Seconds into the current extend and retract cycle
+    elapsed: f32,
+}
+
+#[derive(Component)]
+pub(super) enum FallingBlock {
+    /// Waits for the player to walk below it
+    Hanging { delay: f32 },
+    /// Shakes before it falls
+    Triggered { timer: Timer, origin: Vec2 },
+    Falling {
+        velocity: f32,
+        /// Distance to the ground below, `None` if the block falls out of the level
+        distance_left: Option<f32>,
+    },
+    /// Came to rest on the ground, harmless and solid to stand on
+    Landed,
+}
+
+#[derive(Component)]
+pub(super) struct Crusher {
+    top: f32,
+    travel: f32,
+    wait: f32,
+    phase: CrusherPhase,
+}
+
+enum CrusherPhase {
+    /// Waiting at the top or the bottom before moving on
+    Waiting {
+        timer: Timer,
+        at_top: bool,
+    },
+    Slamming,
+    Rising,
+}
+
+fn damage_event(damage: HazardDamage, target: Entity) -> Damage {
+    match damage {
+        HazardDamage::Hit(amount) => Damage::Hit { target, amount },
+        HazardDamage::Kill => Damage::Kill { target },
+    }
+}
+
+/// A sensor the size of `size` hurting the player while they are inside of it
+fn hazard_sensor(damage: HazardDamage, size: Vec2) -> impl Bundle {
+    (
+        Hazard::new(damage),
+        Collider::cuboid(size.x * 0.5, size.y * 0.5),
+        Sensor,
+        // the player and the moving hazards are kinematic
+        ActiveCollisionTypes::default()
+            | ActiveCollisionTypes::KINEMATIC_STATIC
+            | ActiveCollisionTypes::KINEMATIC_KINEMATIC,
+    )
+}
+
+/// Solid blocks only hurt the player they fall or slam onto, with a sensor along their bottom edge
+fn crushing_edge(damage: HazardDamage, size: Vec2) -> impl Bundle {
+    (
+        TransformBundle::from_transform(Transform::from_xyz(0., -size.y * 0.5, 0.)),
+        hazard_sensor(damage, Vec2::new(size.x, CRUSHING_EDGE_HEIGHT)),
+    )
+}
+
+pub(super) fn spawn_hazards(mut commands: Commands, level: CurrentLevel) {
+    for hazard in &level.data().hazards {
+        let size = hazard.area.size();
+        let center = hazard.area.center();
+        let color = match hazard.kind {
+            HazardKind::Spikes | HazardKind::RetractingSpikes { .. } => SPIKES_COLOR,
+            HazardKind::FallingBlock { .. } | HazardKind::Crusher { .. } => BLOCK_COLOR,
+        };
+        let mut entity = commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(center.extend(0.6)),
+                ..default()
+            },
+            DespawnOnExit(GameState::Playing),
+        ));
+        match hazard.kind {
+            HazardKind::Spikes => {
+                entity.insert(hazard_sensor(hazard.damage, size));
+            }
+            HazardKind::RetractingSpikes {
+                extended,
+                retracted,
+                offset,
+            } => {
+                if extended <= 0. || retracted <= 0. {
+                    warn!(
+                        "Retracting spikes need positive timings, got {extended} and {retracted}"
+                    );
+                }
+                entity.insert((
+                    hazard_sensor(hazard.damage, size),
+                    RetractingSpikes {
+                        extended: extended.max(MIN_SPIKE_PHASE),
+                        retracted: retracted.max(MIN_SPIKE_PHASE),
+                        elapsed: offset.max(0.),
+                    },
+                    AudioEmitter::new(100., 500.),
+                ));
+            }
+            HazardKind::FallingBlock { delay } => {
+                entity
+                    .insert((
+                        FallingBlock::Hanging {
+                            delay: delay.max(0.),
+                        },
+                        RigidBody::KinematicPositionBased,
+                        Collider::cuboid(size.x * 0.5, size.y * 0.5),
+                        AudioEmitter::new(150., 700.),
+                    ))
+                    .with_children(|block| {
+                        block.spawn(crushing_edge(hazard.damage, size));
+                    });
+            }
+            HazardKind::Crusher {
+                travel,
+                wait,
+                offset,
+            } => {
+                let wait = wait.max(0.);
+                entity
+                    .insert((
+                        Crusher {
+                            top: center.y,
+                            travel,
+                            wait,
+                            phase: CrusherPhase::Waiting {
+                                timer: Timer::from_seconds(wait + offset.max(0.), TimerMode::Once),
+                                at_top: true,
+                            },
+                        },
+                        RigidBody::KinematicPositionBased,
+                        Collider::cuboid(size.x * 0.5, size.y * 0.5),
+                        AudioEmitter::new(150., 700.),
+                    ))
+                    .with_children(|crusher| {
+                        crusher.spawn(crushing_edge(hazard.damage, size));
+                    });
+            }
+        }
+    }
+}
+
+/// Hits are sent every frame, the player's invulnerability after a hit spaces them out
+pub(super) fn damage_on_contact(
+    rapier_context: Res<RapierContext>,
+    mut damage: EventWriter<Damage>,
+    hazards: Query<(Entity, &Hazard)>,
+    players: Query<Entity, With<Player>>,
+) {
+    for player in &players {
+        for (entity, hazard) in &hazards {
+            if hazard.active && rapier_context.intersection_pair(player, entity) == Some(true) {
+                damage.send(damage_event(hazard.damage, player));
+            }
+        }
+    }
+}
+
+pub(super) fn retract_spikes(
+    time: Res<Time>,
+    mut sfx: EventWriter<EmitterSfxEvent>,
+    mut spikes: Query<(
+        Entity,
+        &mut RetractingSpikes,
+        &mut Hazard,
+        &mut Transform,
+        &Sprite,
+    )>,
+) {
+    for (entity, mut cycle, mut hazard, mut transform, sprite) in &mut spikes {
+        cycle.elapsed = (cycle.elapsed + time.delta_seconds()) % (cycle.extended + cycle.retracted);
+        let extended = cycle.elapsed < cycle.extended;
+        if hazard.active != extended {
+            hazard.active = extended;
+            // keep the spikes' base on the ground while they shrink
+            let scale = if extended { 1. } else { RETRACTED_SCALE };
+            let height = sprite.custom_size.map_or(0., |size| size.y);
+            transform.translation.y += (scale - transform.scale.y) * 0.5 * height;
+            transform.scale.y = scale;
+            if extended {
+                sfx.send(EmitterSfxEvent {
+                    sfx: SfxEvent::Spikes,
+                    emitter: entity,
+                });
+            }
+        }
+    }
+}
+
+pub(super) fn update_falling_blocks(
+    mut commands: Commands,
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    level: CurrentLevel,
+    mut sfx: EventWriter<EmitterSfxEvent>,
+    mut blocks: Query<(Entity, &mut FallingBlock, &mut Transform, &Sprite)>,
+    players: Query<&Transform, (With<Player>, Without<FallingBlock>)>,
+) {
+    let player = players.get_single().ok();
+    for (entity, mut block, mut transform, sprite) in &mut blocks {
+        let position = transform.translation.truncate();
+        let size = sprite.custom_size.unwrap_or_default();
+        match &mut *block {
+            FallingBlock::Hanging { delay } => {
+                let below = player.is_some_and(|player| {
+                    let offset = player.translation.truncate() - position;
+                    offset.x.abs() < size.x * 0.5
+                        && offset.y < 0.
+                        && offset.y > -FALL_TRIGGER_DISTANCE
+                });
+                if below {
+                    *block = FallingBlock::Triggered {
+                        timer: Timer::from_seconds(*delay, TimerMode::Once),
+                        origin: position,
+                    };
+                }
+            }
+            FallingBlock::Triggered { timer, origin } => {
+                timer.tick(time.delta());
+                let shake = (timer.elapsed_secs() * 60.).sin() * SHAKE_AMPLITUDE;
+                transform.translation.x = origin.x + shake;
+                if timer.finished() {
+                    transform.translation.x = origin.x;
+                    // fall until the block's bottom reaches the ground below it
+                    let bottom = *origin - Vec2::new(0., size.y * 0.5);
+                    let filter = QueryFilter::only_fixed()
+                        .exclude_sensors()
+                        .exclude_collider(entity);
+                    let distance_left = rapier_context
+                        .cast_ray(bottom, Vec2::NEG_Y, f32::MAX, true, filter)
+                        .map(|(_, toi)| toi);
+                    *block = FallingBlock::Falling {
+                        velocity: 0.,
+                        distance_left,
+                    };
+                }
+            }
+            FallingBlock::Falling {
+                velocity,
+                distance_left,
+            } => {
+                *velocity += FALL_ACCELERATION * time.delta_seconds();
+                let mut step = *velocity * time.delta_seconds();
+                if let Some(distance_left) = distance_left {
+                    step = step.min(*distance_left);
+                    *distance_left -= step;
+                }
+                transform.translation.y -= step;
+                match distance_left {
+                    Some(distance_left) if *distance_left <= 0. => {
+                        // the crushing edge goes, the block stays as solid ground
+                        commands.entity(entity).despawn_descendants();
+                        *block = FallingBlock::Landed;
+                        sfx.send(EmitterSfxEvent {
+                            sfx: SfxEvent::Crash,
+                            emitter: entity,
+                        });
+                    }
+                    None if transform.translation.y + size.y * 0.5 < level.data().bottom() => {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                    _ => {}
+                }
+            }
+            FallingBlock::Landed => {}
+        }
+    }
+}
+
+pub(super) fn move_crushers(
+    time: Res<Time>,
+    mut sfx: EventWriter<EmitterSfxEvent>,
+    mut crushers: Query<(Entity, &mut Crusher, &mut Transform)>,
+) {
+    for (entity, mut crusher, mut transform) in &mut crushers {
+        let top = crusher.top;
+        let bottom = top - crusher.travel;
+        let wait = crusher.wait;
+        let next = match &mut crusher.phase {
+            CrusherPhase::Waiting { timer, at_top } => {
+                timer.tick(time.delta());
+                match (timer.finished(), *at_top) {
+                    (true, true) => Some(CrusherPhase::Slamming),
+                    (true, false) => Some(CrusherPhase::Rising),
+                    _ => None,
+                }
+            }
+            CrusherPhase::Slamming => {
+                transform.translation.y -= CRUSHER_SLAM_VELOCITY * time.delta_seconds();
+                (transform.translation.y <= bottom).then(|| {
+                    transform.translation.y = bottom;
+                    sfx.send(EmitterSfxEvent {
+                        sfx: SfxEvent::Crash,
+                        emitter: entity,
+                    });
+                    CrusherPhase::Waiting {
+                        timer: Timer::from_seconds(wait, TimerMode::Once),
+                        at_top: false,
+                    }
+                })
+            }
+            CrusherPhase::Rising => {
+                transform.translation.y += CRUSHER_RISE_VELOCITY * time.delta_seconds();
+                (transform.translation.y >= top).then(|| {
+                    transform.translation.y = top;
+                    CrusherPhase::Waiting {
+                        timer: Timer::from_seconds(wait, TimerMode::Once),
+                        at_top: true,
+                    }
+                })
+            }
+        };
+        if let Some(next) = next {
+            crusher.phase = next;
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::actions::{Actions, GameControl, InputBindings};
+use crate::despawn::DespawnOnExit;
+use crate::health::Dead;
+use crate::player::Player;
+use crate::GameState;
+use bevy::prelude::*;
+
+/// How close the player has to be to an [`Interactable`] to trigger it
+const INTERACT_DISTANCE: f32 = 48.;
+/// Height of the prompt above the interactable's center
+const PROMPT_OFFSET: f32 = 36.;
+const PROMPT_FONT_SIZE: f32 = 16.;
+
+/// Something the player triggers by pressing the interact key close to it
+#[derive(Component)]
+pub struct Interactable;
+
+/// The player interacted with this entity
+#[derive(Event)]
+pub struct Interacted(pub Entity);
+
+/// The interactable the player would trigger right now
+#[derive(Default, Resource)]
+pub(super) struct NearestInteractable(Option<Entity>);
+
+/// Shows the interact key above the [`NearestInteractable`]
+#[derive(Component)]
+pub(super) struct InteractionPrompt;
+
+pub(super) fn spawn_interaction_prompt(mut commands: Commands) {
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: PROMPT_FONT_SIZE,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        InteractionPrompt,
+        DespawnOnExit(GameState::Playing),
+    ));
+}
+
+pub(super) fn find_nearest_interactable(
+    mut nearest: ResMut<NearestInteractable>,
+    players: Query<&Transform, (With<Player>, Without<Dead>)>,
+    interactables: Query<(Entity, &Transform), With<Interactable>>,
+) {
+    let entity = players.get_single().ok().and_then(|player| {
+        let position = player.translation.truncate();
+        interactables
+            .iter()
+            .map(|(entity, transform)| {
+                let distance = transform.translation.truncate().distance(position);
+                (entity, distance)
+            })
+            .filter(|(_, distance)| *distance <= INTERACT_DISTANCE)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(entity, _)| entity)
+    });
+    if nearest.0 != entity {
+        nearest.0 = entity;
+    }
+}
+
+pub(super) fn interact(
+    actions: Res<Actions>,
+    nearest: Res<NearestInteractable>,
+    mut interacted: EventWriter<Interacted>,
+) {
+    if let (true, Some(entity)) = (actions.interact, nearest.0) {
+        interacted.send(Interacted(entity));
+    }
+}
+
+pub(super) fn update_interaction_prompt(
+    nearest: Res<NearestInteractable>,
+    bindings: Res<InputBindings>,
+    interactables: Query<&Transform, (With<Interactable>, Without<InteractionPrompt>)>,
+    mut prompts: Query<(&mut Transform, &mut Visibility, &mut Text), With<InteractionPrompt>>,
+) {
+    let target = nearest.0.and_then(|entity| interactables.get(entity).ok());
+    for (mut transform, mut visibility, mut text) in &mut prompts {
+        let Some(target) = target else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Inherited;
+        transform.translation =
+            target.translation.truncate().extend(5.) + Vec3::new(0., PROMPT_OFFSET, 0.);
+        let key = bindings
+            .keys(GameControl::Interact)
+            .first()
+            .map(|key| format!("[{key:?}]"))
+            .unwrap_or_default();
+        if text.sections[0].value != key {
+            text.sections[0].value = key;
+        }
+    }
+}
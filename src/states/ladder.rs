@@ -0,0 +1,75 @@
+use crate::despawn::DespawnOnExit;
+use crate::player::Player;
+use crate::states::{player_sensor, sensor_and_player, CurrentLevel};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const LADDER_COLOR: Color = Color::rgba(0.55, 0.4, 0.2, 0.7);
+
+/// A ladder or vine the player can climb along
+#[derive(Component, Clone, Copy)]
+pub struct Ladder {
+    /// Horizontal center the climbing player is pulled to
+    pub x: f32,
+    /// World height of the ladder's upper end, the player climbs onto the ground above it there
+    pub top: f32,
+}
+
+/// The player overlaps this ladder and can start climbing it
+#[derive(Component)]
+pub struct NearLadder {
+    pub entity: Entity,
+    pub ladder: Ladder,
+}
+
+pub(super) fn spawn_ladders(mut commands: Commands, level: CurrentLevel) {
+    for ladder in &level.data().ladders {
+        let size = ladder.size();
+        let center = ladder.center();
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: LADDER_COLOR,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(center.extend(0.4)),
+                ..default()
+            },
+            Ladder {
+                x: center.x,
+                top: center.y + size.y * 0.5,
+            },
+            Collider::cuboid(size.x * 0.5, size.y * 0.5),
+            player_sensor(),
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+pub(super) fn track_near_ladders(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionEvent>,
+    ladders: Query<&Ladder>,
+    players: Query<Option<&NearLadder>, With<Player>>,
+) {
+    for collision in collisions.read() {
+        let Some((ladder_entity, player, started)) = sensor_and_player(collision, &ladders) else {
+            continue;
+        };
+        let (Ok(ladder), Ok(near_ladder)) = (ladders.get(ladder_entity), players.get(player))
+        else {
+            continue;
+        };
+        if started {
+            commands.entity(player).insert(NearLadder {
+                entity: ladder_entity,
+                ladder: *ladder,
+            });
+        } else if near_ladder.is_some_and(|near_ladder| near_ladder.entity == ladder_entity) {
+            // leaving a ladder the player already left for an overlapping one keeps the other one
+            commands.entity(player).remove::<NearLadder>();
+        }
+    }
+}
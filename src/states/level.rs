@@ -1,34 +1,182 @@
+use crate::audio::{Music, MusicTrack};
 use crate::camera::camera_fit_inside_current_level;
-use crate::loading::TextureAssets;
-use crate::GameState;
+use crate::cutscene::ActiveCutscene;
+use crate::despawn::DespawnOnExit;
+use crate::dialogue::DialogueData;
+use crate::loading::{LevelAssets, TextureAssets};
+use crate::player::PlayerLabel;
+use crate::ron_asset::RonAssetLoader;
+use crate::states::ability_pickup::{pick_up_abilities, spawn_ability_pickups};
+use crate::states::berry::{animate_picked_berries, pick_up_berries, spawn_berries, BerryCount};
+use crate::states::checkpoint::{reach_checkpoint, spawn_checkpoints};
+use crate::states::chest::{open_chests, spawn_chests};
+use crate::states::door::{spawn_doors, toggle_switches};
+use crate::states::enemy::patrol;
+use crate::states::hazard::{
+    damage_on_contact, move_crushers, retract_spikes, spawn_hazards, update_falling_blocks,
+};
+use crate::states::interact::{
+    find_nearest_interactable, interact, spawn_interaction_prompt, update_interaction_prompt,
+    Interacted, NearestInteractable,
+};
+use crate::states::ladder::{spawn_ladders, track_near_ladders};
+use crate::states::level_data::{CurrentLevel, LevelData};
+use crate::states::moving_platform::{carry_player, move_platforms, spawn_moving_platforms};
+use crate::states::npc::{spawn_npcs, talk_to_npcs};
+use crate::states::platform::{spawn_platforms, update_one_way_platforms};
+use crate::states::respawn::{
+    enter_kill_zones, fade_in_after_respawn, play_death_sequence, spawn_kill_zones, start_dying,
+    update_screen_fade, GameplaySettings, Respawned,
+};
+use crate::states::slope::spawn_slopes;
+use crate::states::trigger::{
+    detect_triggers, run_trigger_actions, spawn_triggers, tick_stay_triggers, TriggerFired,
+};
+use crate::states::water::{animate_droplets, enter_water, hold_breath, spawn_water};
+use crate::{GameState, GameplayLabel};
 use bevy::app::{App, Plugin};
 use bevy::prelude::*;
 use bevy_parallax::ParallaxSystems;
 use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
 
 pub struct LevelPlugin;
 
 impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Playing), setup_level)
+        app.init_asset::<LevelData>()
+            .init_asset_loader::<RonAssetLoader<LevelData>>()
+            .init_resource::<BerryCount>()
+            .init_resource::<GameplaySettings>()
+            .init_resource::<NearestInteractable>()
+            .add_event::<RestartLevel>()
+            .add_event::<Interacted>()
+            .add_event::<TriggerFired>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                (
+                    setup_level,
+                    spawn_checkpoints,
+                    spawn_berries,
+                    spawn_kill_zones,
+                    spawn_platforms,
+                    spawn_moving_platforms,
+                    spawn_slopes,
+                    spawn_ability_pickups,
+                    spawn_ladders,
+                    spawn_water,
+                    spawn_hazards,
+                    spawn_doors,
+                    spawn_chests,
+                    spawn_interaction_prompt,
+                    spawn_triggers,
+                    spawn_npcs,
+                    fade_in_after_respawn.run_if(resource_exists::<Respawned>()),
+                ),
+            )
             .add_systems(
                 Update,
-                camera_fit_inside_current_level
-                    .before(ParallaxSystems)
-                    .run_if(in_state(GameState::Playing)),
-            );
+                (
+                    camera_fit_inside_current_level
+                        .run_if(not(resource_exists::<ActiveCutscene>()))
+                        .before(ParallaxSystems)
+                        .in_set(GameplayLabel),
+                    (
+                        reach_checkpoint,
+                        pick_up_berries,
+                        animate_picked_berries,
+                        pick_up_abilities,
+                        track_near_ladders,
+                        enter_water,
+                        animate_droplets,
+                        hold_breath,
+                        enter_kill_zones,
+                        start_dying,
+                        play_death_sequence,
+                        update_one_way_platforms,
+                        move_platforms,
+                    )
+                        .in_set(GameplayLabel),
+                    (
+                        patrol,
+                        retract_spikes,
+                        update_falling_blocks,
+                        move_crushers,
+                        damage_on_contact,
+                    )
+                        .chain()
+                        // enemies and hazards wait while the player can not move
+                        .run_if(not(resource_exists::<ActiveCutscene>()))
+                        .in_set(GameplayLabel),
+                    (
+                        find_nearest_interactable,
+                        interact,
+                        (toggle_switches, open_chests, talk_to_npcs),
+                        update_interaction_prompt,
+                    )
+                        .chain()
+                        .in_set(GameplayLabel),
+                    (detect_triggers, tick_stay_triggers, run_trigger_actions)
+                        .chain()
+                        .in_set(GameplayLabel),
+                    carry_player.after(PlayerLabel).in_set(GameplayLabel),
+                    update_screen_fade,
+                    restart_level.run_if(on_event::<RestartLevel>()),
+                ),
+            )
+            .add_systems(OnEnter(GameState::Restarting), finish_restart);
     }
 }
 
+/// Tears the current level down and sets it up again from the start
+#[derive(Event)]
+pub struct RestartLevel;
+
 #[derive(Component)]
 pub struct Level;
 
-struct Coordinates {
-    from: Vec2,
-    to: Vec2,
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Serialize, Deserialize,
+)]
+pub enum LevelId {
+    #[default]
+    Level07,
+}
+
+impl LevelId {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LevelId::Level07 => "Level 07",
+        }
+    }
+
+    fn music(&self) -> MusicTrack {
+        match self {
+            LevelId::Level07 => MusicTrack::Level07,
+        }
+    }
+
+    pub fn data<'a>(&self, level_assets: &'a LevelAssets) -> &'a Handle<LevelData> {
+        match self {
+            LevelId::Level07 => &level_assets.level07,
+        }
+    }
+
+    pub fn dialogue<'a>(&self, level_assets: &'a LevelAssets) -> &'a Handle<DialogueData> {
+        match self {
+            LevelId::Level07 => &level_assets.level07_dialogue,
+        }
+    }
 }
 
-fn setup_level(mut cmd: Commands, textures: Res<TextureAssets>) {
+fn setup_level(
+    mut cmd: Commands,
+    textures: Res<TextureAssets>,
+    level: CurrentLevel,
+    mut music: ResMut<Music>,
+) {
+    music.track = Some(level.id().music());
+
     cmd.spawn(SpriteBundle {
         // transform: Transform::from_translation(Vec3::new(512. / 2., 0., 0.)),
         sprite: Sprite {
@@ -38,33 +186,11 @@ fn setup_level(mut cmd: Commands, textures: Res<TextureAssets>) {
         ..Default::default()
     })
     .insert(Level)
+    .insert(DespawnOnExit(GameState::Playing))
     .with_children(|p| {
-        // // 2234
-        // // 228
-        let coords = vec![
-            Coordinates {
-                from: Vec2::new(2234. - 1208., -144.),
-                to: Vec2::new(2416. - 1208., -84.),
-            },
-            Coordinates {
-                from: Vec2::new(1633. - 1208., -144.),
-                to: Vec2::new(2100. - 1208., -84.),
-            },
-            Coordinates {
-                from: Vec2::new(1162. - 1208., -144.),
-                to: Vec2::new(1539. - 1208., -84.),
-            },
-            Coordinates {
-                from: Vec2::new(-1208., -144.),
-                to: Vec2::new(1080. - 1208., -84.),
-            },
-        ];
-
-        for cord in &coords {
-            let from = cord.from;
-            let to = cord.to;
-            let size = from.max(to) - from.min(to);
-            let translation = (from + to) * 0.5;
+        for cord in &level.data().ground {
+            let size = cord.size();
+            let translation = cord.center();
             p.spawn((
                 SpriteBundle {
                     sprite: Sprite {
@@ -172,3 +298,13 @@ fn setup_level(mut cmd: Commands, textures: Res<TextureAssets>) {
         // ));
     });
 }
+
+/// Leaves `GameState::Playing` through a real transition, so state-scoped systems and
+/// run conditions see the level being left and entered again
+fn restart_level(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Restarting);
+}
+
+fn finish_restart(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Playing);
+}
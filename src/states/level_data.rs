@@ -0,0 +1,370 @@
+use crate::audio::{MusicTrack, SfxEvent};
+use crate::cutscene::CutsceneStep;
+use crate::loading::LevelAssets;
+use crate::player::Ability;
+use crate::ron_asset::RonAsset;
+use crate::save::Progress;
+use crate::states::LevelId;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+/// Layout of a level, loaded from a `.level.ron` file
+#[derive(Asset, TypePath, Deserialize)]
+pub struct LevelData {
+    /// Where the player starts if no checkpoint was reached yet
+    pub start: Vec2,
+    /// Solid ground the player can walk on
+    pub ground: Vec<Coordinates>,
+    /// One-way platforms the player can jump up through and drop down from
+    #[serde(default)]
+    pub platforms: Vec<Coordinates>,
+    /// Sloped ground like ramps and hills
+    #[serde(default)]
+    pub slopes: Vec<Outline>,
+    #[serde(default)]
+    pub moving_platforms: Vec<MovingPlatformData>,
+    /// The index of a checkpoint is its id
+    #[serde(default)]
+    pub checkpoints: Vec<Vec2>,
+    /// The index of a berry is its id
+    #[serde(default)]
+    pub berries: Vec<Vec2>,
+    /// Ladders and vines the player can climb
+    #[serde(default)]
+    pub ladders: Vec<Coordinates>,
+    #[serde(default)]
+    pub water: Vec<WaterData>,
+    /// Doors blocking the way until a switch opens them
+    #[serde(default)]
+    pub doors: Vec<DoorData>,
+    #[serde(default)]
+    pub switches: Vec<SwitchData>,
+    #[serde(default)]
+    pub chests: Vec<ChestData>,
+    /// Characters the player can talk to
+    #[serde(default)]
+    pub npcs: Vec<NpcData>,
+    /// Pickups unlocking the player's abilities
+    #[serde(default)]
+    pub abilities: Vec<AbilityPickupData>,
+    /// Spikes, falling blocks and crushers hurting the player on contact
+    #[serde(default)]
+    pub hazards: Vec<HazardData>,
+    /// Areas running actions when the player enters, leaves or stays inside of them
+    #[serde(default)]
+    pub triggers: Vec<TriggerData>,
+    /// Timelines by id, played by triggers
+    #[serde(default)]
+    pub cutscenes: HashMap<String, Vec<CutsceneStep>>,
+    /// Areas that kill the player on contact, e.g. below the ground
+    #[serde(default)]
+    pub kill_zones: Vec<Coordinates>,
+}
+
+impl LevelData {
+    /// The lowest point of the level's ground and kill zones
+    pub fn bottom(&self) -> f32 {
+        self.ground
+            .iter()
+            .chain(&self.kill_zones)
+            .map(|area| area.from.y.min(area.to.y))
+            .fold(self.start.y, f32::min)
+    }
+}
+
+/// A rectangle given by two opposite corners
+#[derive(Deserialize)]
+pub struct Coordinates {
+    pub from: Vec2,
+    pub to: Vec2,
+}
+
+impl Coordinates {
+    pub fn size(&self) -> Vec2 {
+        self.from.max(self.to) - self.from.min(self.to)
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.from + self.to) * 0.5
+    }
+}
+
+/// A body of water the player swims in
+#[derive(Deserialize)]
+pub struct WaterData {
+    pub area: Coordinates,
+    /// Seconds the player can stay under water before drowning, unlimited if `None`
+    #[serde(default)]
+    pub breath: Option<f32>,
+}
+
+#[derive(Deserialize)]
+pub struct DoorData {
+    /// Switches refer to doors by this id, several doors may share one
+    pub id: u32,
+    pub area: Coordinates,
+    #[serde(default)]
+    pub open: bool,
+}
+
+/// A switch toggling the doors with the given ids
+#[derive(Deserialize)]
+pub struct SwitchData {
+    pub position: Vec2,
+    pub doors: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct ChestData {
+    pub position: Vec2,
+    /// Ids of the berries dropped when the chest is opened, they are not placed in the level before
+    pub berries: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct NpcData {
+    pub position: Vec2,
+    /// Id of the dialogue in the level's `.dialogue.ron` file
+    pub dialogue: String,
+}
+
+#[derive(Deserialize)]
+pub struct AbilityPickupData {
+    pub ability: Ability,
+    pub position: Vec2,
+}
+
+/// A hazard, its area is the size and starting position of its sprite
+#[derive(Deserialize)]
+pub struct HazardData {
+    pub kind: HazardKind,
+    pub area: Coordinates,
+    #[serde(default)]
+    pub damage: HazardDamage,
+}
+
+#[derive(Clone, Copy, Deserialize)]
+pub enum HazardKind {
+    Spikes,
+    /// Spikes going in and out of the ground, timings are in seconds
+    RetractingSpikes {
+        extended: f32,
+        retracted: f32,
+        /// Shifts the cycle, so neighbouring spikes can take turns
+        #[serde(default)]
+        offset: f32,
+    },
+    /// Falls down to the ground once the player walks below it
+    FallingBlock {
+        delay: f32,
+    },
+    /// Slams down by `travel` pixels and slowly rises again, waiting `wait` seconds in between
+    Crusher {
+        travel: f32,
+        wait: f32,
+        #[serde(default)]
+        offset: f32,
+    },
+}
+
+/// What touching a hazard does to the player
+#[derive(Clone, Copy, Deserialize)]
+pub enum HazardDamage {
+    /// Takes away that many hearts
+    Hit(u32),
+    Kill,
+}
+
+impl Default for HazardDamage {
+    fn default() -> Self {
+        HazardDamage::Hit(1)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TriggerData {
+    pub area: Coordinates,
+    #[serde(default)]
+    pub on: TriggerOn,
+    /// Only runs the actions the first time, e.g. for ambushes
+    #[serde(default)]
+    pub once: bool,
+    pub actions: Vec<TriggerAction>,
+}
+
+/// When a trigger runs its actions
+#[derive(Clone, Copy, Default, Deserialize)]
+pub enum TriggerOn {
+    #[default]
+    Enter,
+    Exit,
+    /// Every `interval` seconds while the player is inside
+    Stay {
+        interval: f32,
+    },
+}
+
+#[derive(Clone, Deserialize)]
+pub enum TriggerAction {
+    SpawnEnemies(Vec<EnemyData>),
+    PlaySound(SfxEvent),
+    /// `strength` is the initial amplitude in pixels
+    ShakeCamera {
+        duration: f32,
+        strength: f32,
+    },
+    /// Shows the dialogue with this id
+    StartDialogue(String),
+    /// Plays the cutscene with this id
+    PlayCutscene(String),
+    /// Switches to another track, or silence if `None`
+    ChangeMusic(Option<MusicTrack>),
+    /// Keeps the camera's view between these world x coordinates
+    LockCamera {
+        left: f32,
+        right: f32,
+    },
+    UnlockCamera,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct EnemyData {
+    pub position: Vec2,
+    /// How far the enemy walks to either side of its position
+    pub patrol: f32,
+    /// Pixels per second
+    pub speed: f32,
+}
+
+/// Collision shape of sloped ground, in world coordinates
+#[derive(Deserialize)]
+pub enum Outline {
+    /// A line the player walks on, it is part of the level's art and not drawn
+    Polyline(Vec<Vec2>),
+    /// A convex shape, drawn like the platforms
+    Polygon(Vec<Vec2>),
+}
+
+/// A platform following a path through its waypoints
+#[derive(Deserialize)]
+pub struct MovingPlatformData {
+    pub size: Vec2,
+    /// The platform starts at the first waypoint, at least two are needed to move
+    pub waypoints: Vec<Vec2>,
+    /// Pixels per second, before easing
+    pub speed: f32,
+    #[serde(default)]
+    pub path: PathMode,
+    #[serde(default)]
+    pub easing: Easing,
+}
+
+/// What a moving platform does after reaching its last waypoint
+#[derive(Clone, Copy, Default, Deserialize)]
+pub enum PathMode {
+    /// Stops at the last waypoint
+    Linear,
+    /// Goes back through the waypoints in reverse order
+    #[default]
+    PingPong,
+    /// Goes on from the last waypoint to the first one
+    Loop,
+}
+
+/// How a moving platform speeds up and slows down between two waypoints
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps the progress `t` between two waypoints to the distance covered, both from 0 to 1
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1. - (1. - t) * (1. - t),
+            Easing::EaseInOut => t * t * (3. - 2. * t),
+        }
+    }
+}
+
+impl RonAsset for LevelData {
+    const EXTENSIONS: &'static [&'static str] = &["level.ron"];
+}
+
+/// The [`LevelData`] of the level in the player's [`Progress`]
+#[derive(SystemParam)]
+pub struct CurrentLevel<'w> {
+    progress: Res<'w, Progress>,
+    level_assets: Res<'w, LevelAssets>,
+    levels: Res<'w, Assets<LevelData>>,
+}
+
+impl<'w> CurrentLevel<'w> {
+    pub fn id(&self) -> LevelId {
+        self.progress.level
+    }
+
+    pub fn data(&self) -> &LevelData {
+        self.levels
+            .get(self.progress.level.data(&self.level_assets))
+            .expect("levels are loaded during GameState::Loading")
+    }
+
+    /// The position the player is spawned at, the last checkpoint reached or the start of the level
+    pub fn spawn_point(&self) -> Vec2 {
+        let data = self.data();
+        self.progress
+            .checkpoint
+            .and_then(|id| data.checkpoints.get(id as usize))
+            .copied()
+            .unwrap_or(data.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EASINGS: [Easing; 4] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ];
+
+    #[test]
+    fn easings_start_and_end_at_the_waypoints() {
+        for easing in EASINGS {
+            assert_eq!(easing.apply(0.), 0., "{easing:?}");
+            assert_eq!(easing.apply(1.), 1., "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn easings_never_move_backwards() {
+        for easing in EASINGS {
+            let mut last = 0.;
+            for step in 1..=20 {
+                let distance = easing.apply(step as f32 / 20.);
+                assert!(distance >= last, "{easing:?}");
+                last = distance;
+            }
+        }
+    }
+
+    #[test]
+    fn ease_in_starts_slow_and_ease_out_ends_slow() {
+        assert!(Easing::EaseIn.apply(0.25) < Easing::Linear.apply(0.25));
+        assert!(Easing::EaseOut.apply(0.75) > Easing::Linear.apply(0.75));
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+}
@@ -1,3 +1,59 @@
+mod ability_pickup;
+mod berry;
+mod checkpoint;
+mod chest;
+mod door;
+mod enemy;
+mod hazard;
+mod interact;
+mod ladder;
 mod level;
+mod level_data;
+mod moving_platform;
+mod npc;
+mod platform;
+mod respawn;
+mod slope;
+mod trigger;
+mod water;
 
-pub use level::{Level, LevelPlugin};
+pub use berry::BerryCount;
+pub use ladder::NearLadder;
+pub use level::{Level, LevelId, LevelPlugin, RestartLevel};
+pub use level_data::{CurrentLevel, LevelData};
+pub use platform::{DropThrough, OneWayPlatform, OneWayPlatformHooks, PLAYER_GROUP};
+pub use respawn::{GameplaySettings, START_LIVES};
+pub use water::InWater;
+
+use bevy::ecs::query::{ReadOnlyWorldQuery, WorldQuery};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Makes a collider a sensor sending [`CollisionEvent`]s when the player enters or leaves it
+fn player_sensor() -> impl Bundle {
+    (
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+        // the player is moved by a kinematic character controller
+        ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_STATIC,
+    )
+}
+
+/// The entity in `sensors` and the other entity of a collision, and whether it started or stopped
+/// `None` if neither entity is in `sensors`
+fn sensor_and_player<Q: WorldQuery, F: ReadOnlyWorldQuery>(
+    collision: &CollisionEvent,
+    sensors: &Query<Q, F>,
+) -> Option<(Entity, Entity, bool)> {
+    let (first, second, started) = match collision {
+        CollisionEvent::Started(first, second, _) => (*first, *second, true),
+        CollisionEvent::Stopped(first, second, _) => (*first, *second, false),
+    };
+    if sensors.contains(first) {
+        Some((first, second, started))
+    } else if sensors.contains(second) {
+        Some((second, first, started))
+    } else {
+        None
+    }
+}
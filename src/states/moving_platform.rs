@@ -0,0 +1,252 @@
+use crate::despawn::DespawnOnExit;
+use crate::player::Player;
+use crate::states::level_data::{Easing, PathMode};
+use crate::states::platform::PLATFORM_COLOR;
+use crate::states::CurrentLevel;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// How much of the momentum taken from a platform is left after one second in the air
+const AIR_MOMENTUM_RETAINED: f32 = 0.5;
+
+/// A kinematic platform following the waypoints from the level data
+#[derive(Component)]
+pub(super) struct MovingPlatform {
+    waypoints: Vec<Vec2>,
+    speed: f32,
+    path: PathMode,
+    easing: Easing,
+    /// Index of the waypoint the platform comes from
+    from: usize,
+    /// Index of the waypoint the platform moves to
+    to: usize,
+    /// Progress between `from` and `to`, from 0 to 1
+    progress: f32,
+    /// Only used by `PathMode::PingPong`
+    forward: bool,
+    /// Pixels per second during the last frame
+    velocity: Vec2,
+}
+
+impl MovingPlatform {
+    fn position(&self) -> Vec2 {
+        let from = self.waypoints[self.from];
+        let to = self.waypoints[self.to];
+        from.lerp(to, self.easing.apply(self.progress))
+    }
+
+    /// Picks the next waypoint once `to` was reached, `None` at the end of a linear path
+    fn next_waypoint(&mut self) -> Option<usize> {
+        let last = self.waypoints.len() - 1;
+        match self.path {
+            PathMode::Linear => (self.to < last).then_some(self.to + 1),
+            PathMode::Loop => Some((self.to + 1) % self.waypoints.len()),
+            PathMode::PingPong => {
+                if self.to == last {
+                    self.forward = false;
+                } else if self.to == 0 {
+                    self.forward = true;
+                }
+                Some(if self.forward {
+                    self.to + 1
+                } else {
+                    self.to - 1
+                })
+            }
+        }
+    }
+
+    fn advance(&mut self, seconds: f32) {
+        if self.from == self.to {
+            return;
+        }
+        let length = self.waypoints[self.from].distance(self.waypoints[self.to]);
+        self.progress += self.speed * seconds / length.max(f32::EPSILON);
+        if self.progress < 1. {
+            return;
+        }
+        match self.next_waypoint() {
+            Some(next) => {
+                self.from = self.to;
+                self.to = next;
+                self.progress = (self.progress - 1.).min(1.);
+            }
+            None => self.progress = 1.,
+        }
+    }
+}
+
+/// Velocity the player took over from a moving platform
+/// Kept while jumping off the platform, until the player lands somewhere else
+#[derive(Component)]
+pub(super) struct Carried(Vec2);
+
+pub(super) fn spawn_moving_platforms(mut commands: Commands, level: CurrentLevel) {
+    for platform in &level.data().moving_platforms {
+        let Some(start) = platform.waypoints.first() else {
+            continue;
+        };
+        let size = platform.size;
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: PLATFORM_COLOR,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(start.extend(0.5)),
+                ..default()
+            },
+            MovingPlatform {
+                waypoints: platform.waypoints.clone(),
+                speed: platform.speed,
+                path: platform.path,
+                easing: platform.easing,
+                from: 0,
+                to: 1.min(platform.waypoints.len() - 1),
+                progress: 0.,
+                forward: true,
+                velocity: Vec2::ZERO,
+            },
+            RigidBody::KinematicPositionBased,
+            Collider::cuboid(size.x * 0.5, size.y * 0.5),
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+pub(super) fn move_platforms(
+    time: Res<Time>,
+    mut platforms: Query<(&mut MovingPlatform, &mut Transform)>,
+) {
+    let seconds = time.delta_seconds();
+    if seconds == 0. {
+        return;
+    }
+    for (mut platform, mut transform) in &mut platforms {
+        let before = platform.position();
+        platform.advance(seconds);
+        let after = platform.position();
+        platform.velocity = (after - before) / seconds;
+        transform.translation = after.extend(transform.translation.z);
+    }
+}
+
+/// Moves the player along with the platform it stands on
+/// Runs after the player's own movement, which overwrites the controller's translation
+pub(super) fn carry_player(
+    mut commands: Commands,
+    time: Res<Time>,
+    platforms: Query<&MovingPlatform>,
+    mut players: Query<
+        (
+            Entity,
+            &mut KinematicCharacterController,
+            &KinematicCharacterControllerOutput,
+            Option<&mut Carried>,
+        ),
+        With<Player>,
+    >,
+) {
+    let seconds = time.delta_seconds();
+    for (player, mut controller, output, carried) in &mut players {
+        let platform = output
+            .collisions
+            .iter()
+            .find_map(|collision| platforms.get(collision.entity).ok());
+        let velocity = match (output.grounded, platform, carried) {
+            (true, Some(platform), Some(mut carried)) => {
+                carried.0 = platform.velocity;
+                carried.0
+            }
+            (true, Some(platform), None) => {
+                commands.entity(player).insert(Carried(platform.velocity));
+                platform.velocity
+            }
+            (true, None, Some(_)) => {
+                // landed on solid ground
+                commands.entity(player).remove::<Carried>();
+                continue;
+            }
+            (false, _, Some(mut carried)) => {
+                // jumping and falling only keep the horizontal momentum
+                carried.0.y = 0.;
+                carried.0.x *= AIR_MOMENTUM_RETAINED.powf(seconds);
+                carried.0
+            }
+            _ => continue,
+        };
+        let translation = controller.translation.unwrap_or_default();
+        controller.translation = Some(translation + velocity * seconds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn platform(path: PathMode, waypoint_count: usize) -> MovingPlatform {
+        MovingPlatform {
+            waypoints: (0..waypoint_count)
+                .map(|index| Vec2::new(index as f32 * 100., 0.))
+                .collect(),
+            speed: 100.,
+            path,
+            easing: Easing::Linear,
+            from: 0,
+            to: 1,
+            progress: 0.,
+            forward: true,
+            velocity: Vec2::ZERO,
+        }
+    }
+
+    /// Collects the waypoints the platform heads to after `to`, one per reached waypoint
+    fn visit(platform: &mut MovingPlatform, count: usize) -> Vec<Option<usize>> {
+        (0..count)
+            .map(|_| {
+                let next = platform.next_waypoint();
+                if let Some(next) = next {
+                    platform.from = platform.to;
+                    platform.to = next;
+                }
+                next
+            })
+            .collect()
+    }
+
+    #[test]
+    fn linear_path_stops_at_last_waypoint() {
+        let mut platform = platform(PathMode::Linear, 3);
+
+        assert_eq!(visit(&mut platform, 3), [Some(2), None, None]);
+    }
+
+    #[test]
+    fn loop_path_returns_to_first_waypoint() {
+        let mut platform = platform(PathMode::Loop, 3);
+
+        assert_eq!(visit(&mut platform, 3), [Some(2), Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn ping_pong_path_turns_at_both_ends() {
+        let mut platform = platform(PathMode::PingPong, 3);
+
+        assert_eq!(
+            visit(&mut platform, 5),
+            [Some(2), Some(1), Some(0), Some(1), Some(2)]
+        );
+    }
+
+    #[test]
+    fn advance_carries_leftover_progress_to_next_segment() {
+        let mut platform = platform(PathMode::Linear, 3);
+
+        platform.advance(1.5);
+
+        assert_eq!((platform.from, platform.to), (1, 2));
+        assert!((platform.position().x - 150.).abs() < 1e-3);
+    }
+}
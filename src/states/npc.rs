@@ -0,0 +1,44 @@
+use crate::despawn::DespawnOnExit;
+use crate::dialogue::StartDialogue;
+use crate::states::interact::{Interactable, Interacted};
+use crate::states::CurrentLevel;
+use crate::GameState;
+use bevy::prelude::*;
+
+const NPC_SIZE: Vec2 = Vec2::new(20., 36.);
+const NPC_COLOR: Color = Color::rgb(0.3, 0.55, 0.7);
+
+/// Starts the dialogue with this id when the player interacts with it
+#[derive(Component)]
+pub(super) struct Npc(String);
+
+pub(super) fn spawn_npcs(mut commands: Commands, level: CurrentLevel) {
+    for npc in &level.data().npcs {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: NPC_COLOR,
+                    custom_size: Some(NPC_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(npc.position.extend(0.6)),
+                ..default()
+            },
+            Npc(npc.dialogue.clone()),
+            Interactable,
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+pub(super) fn talk_to_npcs(
+    mut interacted: EventReader<Interacted>,
+    mut dialogue: EventWriter<StartDialogue>,
+    npcs: Query<&Npc>,
+) {
+    for &Interacted(entity) in interacted.read() {
+        if let Ok(Npc(id)) = npcs.get(entity) {
+            dialogue.send(StartDialogue(id.clone()));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::despawn::DespawnOnExit;
+use crate::player::{Player, PLAYER_HALF_HEIGHT};
+use crate::states::CurrentLevel;
+use crate::GameState;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_rapier2d::rapier::prelude::{RigidBodyHandle, SolverFlags};
+
+/// Collision group of the player's character controller
+/// One-way platforms leave it out of their filter while the player should pass through
+pub const PLAYER_GROUP: Group = Group::GROUP_1;
+
+pub(super) const PLATFORM_COLOR: Color = Color::rgb(0.45, 0.3, 0.2);
+/// How far the player's feet may sink into a platform and still land on it
+const LANDING_TOLERANCE: f32 = 2.;
+
+/// Ground the player can jump up through and land on
+/// Rigid bodies are filtered by [`OneWayPlatformHooks`], but bevy_rapier's character controller
+/// casts its shape without consulting physics hooks, so the player is filtered through the
+/// platform's [`CollisionGroups`] instead
+#[derive(Component)]
+pub struct OneWayPlatform {
+    /// World height of the platform's top edge
+    top: f32,
+}
+
+impl OneWayPlatform {
+    fn is_below(&self, feet: f32) -> bool {
+        feet >= self.top - LANDING_TOLERANCE
+    }
+}
+
+/// The player dropped through this platform by pressing down and jump
+/// Removed once the player's feet are below the platform's top
+#[derive(Component)]
+pub struct DropThrough(pub Entity);
+
+/// Lets dynamic bodies land on one-way platforms from above and pass through them otherwise
+#[derive(SystemParam)]
+pub struct OneWayPlatformHooks<'w, 's> {
+    platforms: Query<'w, 's, (), With<OneWayPlatform>>,
+}
+
+impl BevyPhysicsHooks for OneWayPlatformHooks<'_, '_> {
+    fn filter_contact_pair(&self, context: PairFilterContextView) -> Option<SolverFlags> {
+        let raw = context.raw;
+        // replacing the default filter, which skips pairs without a dynamic body
+        let dynamic = |body: Option<RigidBodyHandle>| {
+            body.is_some_and(|handle| raw.bodies[handle].is_dynamic())
+        };
+        if !dynamic(raw.rigid_body1) && !dynamic(raw.rigid_body2) {
+            return None;
+        }
+        let (platform, other) = if self.platforms.contains(context.collider1()) {
+            (raw.collider1, raw.collider2)
+        } else if self.platforms.contains(context.collider2()) {
+            (raw.collider2, raw.collider1)
+        } else {
+            return Some(SolverFlags::COMPUTE_IMPULSES);
+        };
+        let platform = raw.colliders[platform].compute_aabb();
+        let other = raw.colliders[other].compute_aabb();
+        // bodies resting on or falling onto the platform have their bottom in its upper half
+        (other.mins.y >= platform.center().y).then_some(SolverFlags::COMPUTE_IMPULSES)
+    }
+}
+
+fn collision_groups(solid: bool) -> CollisionGroups {
+    if solid {
+        CollisionGroups::new(Group::ALL, Group::ALL)
+    } else {
+        CollisionGroups::new(Group::ALL, Group::ALL - PLAYER_GROUP)
+    }
+}
+
+pub(super) fn spawn_platforms(mut commands: Commands, level: CurrentLevel) {
+    for platform in &level.data().platforms {
+        let size = platform.size();
+        let center = platform.center();
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: PLATFORM_COLOR,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_translation(center.extend(0.5)),
+                ..default()
+            },
+            OneWayPlatform {
+                top: center.y + size.y * 0.5,
+            },
+            RigidBody::Fixed,
+            Collider::cuboid(size.x * 0.5, size.y * 0.5),
+            collision_groups(false),
+            ActiveHooks::FILTER_CONTACT_PAIRS,
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+/// Platforms are only solid for the player while standing on or falling onto them
+pub(super) fn update_one_way_platforms(
+    mut commands: Commands,
+    players: Query<(Entity, &Transform, Option<&DropThrough>), With<Player>>,
+    mut platforms: Query<(Entity, &OneWayPlatform, &mut CollisionGroups)>,
+) {
+    let Ok((player, transform, drop_through)) = players.get_single() else {
+        return;
+    };
+    let feet = transform.translation.y - PLAYER_HALF_HEIGHT;
+    for (entity, platform, mut groups) in &mut platforms {
+        let dropping = drop_through.is_some_and(|drop_through| drop_through.0 == entity);
+        if dropping && !platform.is_below(feet) {
+            commands.entity(player).remove::<DropThrough>();
+        }
+        let target = collision_groups(platform.is_below(feet) && !dropping);
+        if *groups != target {
+            *groups = target;
+        }
+    }
+}
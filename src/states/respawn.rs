@@ -0,0 +1,173 @@
+use crate::despawn::DespawnOnExit;
+use crate::health::{Damage, Dead};
+use crate::player::{Player, MAX_HEALTH};
+use crate::save::{Progress, SaveGame};
+use crate::states::{player_sensor, sensor_and_player, CurrentLevel, RestartLevel};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Lives at the start of a game, if lives are enabled
+pub const START_LIVES: u32 = 3;
+/// Seconds the dying player is shown before the screen fades out
+const DEATH_DURATION: f32 = 1.;
+const FADE_DURATION: f32 = 0.5;
+const DEATH_COLOR: Color = Color::rgb(1., 0.3, 0.3);
+
+/// Options for how forgiving the game is
+#[derive(Clone, Debug, Default, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameplaySettings {
+    /// Limit how often the player can die before the game is over
+    pub lives: bool,
+    /// Show how long the current level has been played for in the HUD
+    pub show_timer: bool,
+}
+
+/// Falling into a kill zone kills the player right away
+#[derive(Component)]
+pub(super) struct KillZone;
+
+/// Plays the death animation of the player
+#[derive(Component)]
+pub(super) struct Dying(Timer);
+
+/// Full screen overlay hiding the level while the player is respawned
+#[derive(Component)]
+pub(super) struct ScreenFade {
+    timer: Timer,
+    fading_out: bool,
+}
+
+/// The level was restarted after the player died, its start is faded in
+#[derive(Resource)]
+pub(super) struct Respawned;
+
+fn spawn_screen_fade(commands: &mut Commands, fading_out: bool) {
+    let alpha = if fading_out { 0. } else { 1. };
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            background_color: Color::BLACK.with_a(alpha).into(),
+            z_index: ZIndex::Global(20),
+            ..default()
+        },
+        ScreenFade {
+            timer: Timer::from_seconds(FADE_DURATION, TimerMode::Once),
+            fading_out,
+        },
+        DespawnOnExit(GameState::Playing),
+    ));
+}
+
+pub(super) fn spawn_kill_zones(mut commands: Commands, level: CurrentLevel) {
+    for zone in &level.data().kill_zones {
+        let size = zone.size();
+        commands.spawn((
+            TransformBundle::from_transform(Transform::from_translation(zone.center().extend(0.))),
+            KillZone,
+            Collider::cuboid(size.x * 0.5, size.y * 0.5),
+            player_sensor(),
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+pub(super) fn enter_kill_zones(
+    mut collisions: EventReader<CollisionEvent>,
+    mut damage: EventWriter<Damage>,
+    kill_zones: Query<(), With<KillZone>>,
+    players: Query<(), With<Player>>,
+) {
+    for collision in collisions.read() {
+        let Some((_, player, true)) = sensor_and_player(collision, &kill_zones) else {
+            continue;
+        };
+        if players.contains(player) {
+            damage.send(Damage::Kill { target: player });
+        }
+    }
+}
+
+pub(super) fn start_dying(
+    mut commands: Commands,
+    players: Query<Entity, (With<Player>, Added<Dead>)>,
+) {
+    for player in &players {
+        commands
+            .entity(player)
+            .insert(Dying(Timer::from_seconds(DEATH_DURATION, TimerMode::Once)));
+    }
+}
+
+pub(super) fn play_death_sequence(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut players: Query<(&mut Dying, &mut TextureAtlasSprite), With<Player>>,
+) {
+    for (mut dying, mut sprite) in &mut players {
+        dying.0.tick(time.delta());
+        sprite.color = DEATH_COLOR.with_a(1. - dying.0.percent());
+        if dying.0.just_finished() {
+            spawn_screen_fade(&mut commands, true);
+        }
+    }
+}
+
+/// Once the screen is black, the level is restarted at the last checkpoint or the game is over
+pub(super) fn update_screen_fade(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<GameplaySettings>,
+    mut progress: ResMut<Progress>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut restart_level: EventWriter<RestartLevel>,
+    mut save_game: EventWriter<SaveGame>,
+    mut fades: Query<(Entity, &mut ScreenFade, &mut BackgroundColor)>,
+) {
+    for (entity, mut fade, mut color) in &mut fades {
+        fade.timer.tick(time.delta());
+        let alpha = if fade.fading_out {
+            fade.timer.percent()
+        } else {
+            fade.timer.percent_left()
+        };
+        color.0 = Color::BLACK.with_a(alpha);
+        if !fade.timer.finished() {
+            continue;
+        }
+        if !fade.fading_out {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        progress.health = MAX_HEALTH;
+        if settings.lives {
+            progress.lives = progress.lives.saturating_sub(1);
+        }
+        if settings.lives && progress.lives == 0 {
+            // start over from the beginning of the level
+            progress.lives = START_LIVES;
+            progress.checkpoint = None;
+            save_game.send(SaveGame);
+            next_state.set(GameState::GameOver);
+        } else {
+            commands.insert_resource(Respawned);
+            restart_level.send(RestartLevel);
+        }
+        // the overlay is despawned with the level, until then it must not finish again
+        fade.fading_out = false;
+        fade.timer.reset();
+    }
+}
+
+pub(super) fn fade_in_after_respawn(mut commands: Commands) {
+    commands.remove_resource::<Respawned>();
+    spawn_screen_fade(&mut commands, false);
+}
@@ -0,0 +1,61 @@
+use crate::despawn::DespawnOnExit;
+use crate::states::level_data::Outline;
+use crate::states::platform::PLATFORM_COLOR;
+use crate::states::CurrentLevel;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::prelude::*;
+
+/// Triangle fan covering a convex polygon
+fn polygon_mesh(points: &[Vec2]) -> Mesh {
+    let positions: Vec<[f32; 3]> = points.iter().map(|point| [point.x, point.y, 0.]).collect();
+    let indices = (1..points.len() as u32 - 1)
+        .flat_map(|index| [0, index, index + 1])
+        .collect();
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0., 0., 1.]; positions.len()]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0., 0.]; positions.len()]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+pub(super) fn spawn_slopes(
+    mut commands: Commands,
+    level: CurrentLevel,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for outline in &level.data().slopes {
+        match outline {
+            Outline::Polyline(points) if points.len() >= 2 => {
+                commands.spawn((
+                    TransformBundle::default(),
+                    RigidBody::Fixed,
+                    Collider::polyline(points.clone(), None),
+                    DespawnOnExit(GameState::Playing),
+                ));
+            }
+            Outline::Polygon(points) if points.len() >= 3 => {
+                let Some(collider) = Collider::convex_hull(points) else {
+                    warn!("Skipping a degenerate slope polygon {points:?}");
+                    continue;
+                };
+                commands.spawn((
+                    MaterialMesh2dBundle {
+                        mesh: meshes.add(polygon_mesh(points)).into(),
+                        material: materials.add(ColorMaterial::from(PLATFORM_COLOR)),
+                        transform: Transform::from_xyz(0., 0., 0.5),
+                        ..default()
+                    },
+                    RigidBody::Fixed,
+                    collider,
+                    DespawnOnExit(GameState::Playing),
+                ));
+            }
+            _ => warn!("Skipping a slope with too few points"),
+        }
+    }
+}
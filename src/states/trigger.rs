@@ -0,0 +1,133 @@
+use crate::audio::{Music, SfxEvent};
+use crate::camera::{CameraBounds, CameraShake};
+use crate::cutscene::StartCutscene;
+use crate::despawn::DespawnOnExit;
+use crate::dialogue::StartDialogue;
+use crate::player::Player;
+use crate::states::enemy::spawn_enemy;
+use crate::states::level_data::{TriggerAction, TriggerOn};
+use crate::states::{player_sensor, sensor_and_player, CurrentLevel};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Runs its actions when the player enters, leaves or stays inside of it
+#[derive(Component)]
+pub(super) struct Trigger {
+    on: TriggerOn,
+    once: bool,
+    actions: Vec<TriggerAction>,
+    /// Repeats the actions of a [`TriggerOn::Stay`] trigger while the player is inside
+    stay_timer: Option<Timer>,
+    fired: bool,
+}
+
+/// A trigger's actions are due
+#[derive(Event)]
+pub(super) struct TriggerFired(Entity);
+
+pub(super) fn spawn_triggers(mut commands: Commands, level: CurrentLevel) {
+    for trigger in &level.data().triggers {
+        let size = trigger.area.size();
+        commands.spawn((
+            TransformBundle::from_transform(Transform::from_translation(
+                trigger.area.center().extend(0.),
+            )),
+            Trigger {
+                on: trigger.on,
+                once: trigger.once,
+                actions: trigger.actions.clone(),
+                stay_timer: None,
+                fired: false,
+            },
+            Collider::cuboid(size.x * 0.5, size.y * 0.5),
+            player_sensor(),
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+pub(super) fn detect_triggers(
+    mut collisions: EventReader<CollisionEvent>,
+    mut fired: EventWriter<TriggerFired>,
+    mut triggers: Query<&mut Trigger>,
+    players: Query<(), With<Player>>,
+) {
+    for collision in collisions.read() {
+        let Some((trigger, player, entered)) = sensor_and_player(collision, &triggers) else {
+            continue;
+        };
+        if !players.contains(player) {
+            continue;
+        }
+        let Ok(mut state) = triggers.get_mut(trigger) else {
+            continue;
+        };
+        match state.on {
+            TriggerOn::Enter if entered => fired.send(TriggerFired(trigger)),
+            TriggerOn::Exit if !entered => fired.send(TriggerFired(trigger)),
+            TriggerOn::Stay { interval } => {
+                state.stay_timer =
+                    entered.then(|| Timer::from_seconds(interval, TimerMode::Repeating));
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(super) fn tick_stay_triggers(
+    time: Res<Time>,
+    mut fired: EventWriter<TriggerFired>,
+    mut triggers: Query<(Entity, &mut Trigger)>,
+) {
+    for (entity, mut trigger) in &mut triggers {
+        let Some(timer) = &mut trigger.stay_timer else {
+            continue;
+        };
+        if timer.tick(time.delta()).just_finished() {
+            fired.send(TriggerFired(entity));
+        }
+    }
+}
+
+/// Executes the actions of fired triggers in the order they are listed
+#[allow(clippy::too_many_arguments)]
+pub(super) fn run_trigger_actions(
+    mut commands: Commands,
+    mut fired: EventReader<TriggerFired>,
+    mut triggers: Query<&mut Trigger>,
+    mut sfx: EventWriter<SfxEvent>,
+    mut dialogue: EventWriter<StartDialogue>,
+    mut cutscene: EventWriter<StartCutscene>,
+    mut music: ResMut<Music>,
+    mut shake: ResMut<CameraShake>,
+    mut bounds: ResMut<CameraBounds>,
+) {
+    for &TriggerFired(entity) in fired.read() {
+        let Ok(mut trigger) = triggers.get_mut(entity) else {
+            continue;
+        };
+        if trigger.once && trigger.fired {
+            continue;
+        }
+        trigger.fired = true;
+        for action in &trigger.actions {
+            match action {
+                TriggerAction::SpawnEnemies(enemies) => {
+                    for enemy in enemies {
+                        spawn_enemy(&mut commands, enemy);
+                    }
+                }
+                TriggerAction::PlaySound(sound) => sfx.send(*sound),
+                TriggerAction::ShakeCamera { duration, strength } => {
+                    shake.start(*duration, *strength)
+                }
+                TriggerAction::StartDialogue(id) => dialogue.send(StartDialogue(id.clone())),
+                TriggerAction::PlayCutscene(id) => cutscene.send(StartCutscene(id.clone())),
+                TriggerAction::ChangeMusic(track) => music.track = *track,
+                TriggerAction::LockCamera { left, right } => bounds.0 = Some((*left, *right)),
+                TriggerAction::UnlockCamera => bounds.0 = None,
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::audio::{AudioEmitter, EmitterSfxEvent, SfxEvent};
+use crate::despawn::DespawnOnExit;
+use crate::health::Damage;
+use crate::player::{Player, PLAYER_HALF_HEIGHT};
+use crate::states::{player_sensor, sensor_and_player, CurrentLevel};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+const WATER_COLOR: Color = Color::rgba(0.2, 0.45, 0.9, 0.45);
+const DROPLET_COLOR: Color = Color::rgba(0.7, 0.85, 1., 0.9);
+const DROPLET_SIZE: Vec2 = Vec2::new(4., 4.);
+const DROPLETS_PER_SPLASH: usize = 8;
+const DROPLET_LIFETIME: f32 = 0.5;
+const DROPLET_GRAVITY: f32 = 600.;
+/// Seconds between hits once the player ran out of breath
+const DROWNING_INTERVAL: f32 = 1.;
+
+/// A body of water the player swims in
+#[derive(Component, Clone, Copy)]
+pub struct Water {
+    /// World height of the water's surface
+    pub surface: f32,
+    /// Seconds the player can stay under water before drowning, unlimited if `None`
+    breath: Option<f32>,
+}
+
+/// The player is in this water and swims instead of running and jumping
+#[derive(Component)]
+pub struct InWater {
+    pub entity: Entity,
+    pub water: Water,
+}
+
+/// Seconds left until the player starts drowning, only counts down with the head under water
+#[derive(Component)]
+pub(super) struct Breath(f32);
+
+/// A drop of a splash, flying up and falling back while it fades out
+#[derive(Component)]
+pub(super) struct Droplet {
+    velocity: Vec2,
+    timer: Timer,
+}
+
+pub(super) fn spawn_water(mut commands: Commands, level: CurrentLevel) {
+    for water in &level.data().water {
+        let size = water.area.size();
+        let center = water.area.center();
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: WATER_COLOR,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                // in front of the player
+                transform: Transform::from_translation(center.extend(1.5)),
+                ..default()
+            },
+            Water {
+                surface: center.y + size.y * 0.5,
+                breath: water.breath,
+            },
+            Collider::cuboid(size.x * 0.5, size.y * 0.5),
+            player_sensor(),
+            // splashes anywhere in the water are heard at full volume
+            AudioEmitter::new(size.x * 0.5 + 150., size.x * 0.5 + 600.),
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+fn spawn_splash(commands: &mut Commands, position: Vec2) {
+    for index in 0..DROPLETS_PER_SPLASH {
+        // spread the droplets evenly from left to right
+        let spread = index as f32 / (DROPLETS_PER_SPLASH - 1) as f32 * 2. - 1.;
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: DROPLET_COLOR,
+                    custom_size: Some(DROPLET_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(1.6)),
+                ..default()
+            },
+            Droplet {
+                velocity: Vec2::new(spread * 90., 220. - spread.abs() * 80.),
+                timer: Timer::from_seconds(DROPLET_LIFETIME, TimerMode::Once),
+            },
+            DespawnOnExit(GameState::Playing),
+        ));
+    }
+}
+
+pub(super) fn enter_water(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionEvent>,
+    mut sfx: EventWriter<EmitterSfxEvent>,
+    waters: Query<&Water>,
+    players: Query<(&Transform, Option<&InWater>), With<Player>>,
+) {
+    for collision in collisions.read() {
+        let Some((water_entity, player, entered)) = sensor_and_player(collision, &waters) else {
+            continue;
+        };
+        let (Ok(water), Ok((transform, in_water))) =
+            (waters.get(water_entity), players.get(player))
+        else {
+            continue;
+        };
+        if entered {
+            commands.entity(player).insert((
+                InWater {
+                    entity: water_entity,
+                    water: *water,
+                },
+                Breath(water.breath.unwrap_or_default()),
+            ));
+        } else if in_water.is_some_and(|in_water| in_water.entity == water_entity) {
+            commands.entity(player).remove::<(InWater, Breath)>();
+        } else {
+            continue;
+        }
+        spawn_splash(
+            &mut commands,
+            Vec2::new(transform.translation.x, water.surface),
+        );
+        sfx.send(EmitterSfxEvent {
+            sfx: SfxEvent::Splash,
+            emitter: water_entity,
+        });
+    }
+}
+
+pub(super) fn animate_droplets(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut droplets: Query<(Entity, &mut Droplet, &mut Transform, &mut Sprite)>,
+) {
+    let seconds = time.delta_seconds();
+    for (entity, mut droplet, mut transform, mut sprite) in &mut droplets {
+        droplet.timer.tick(time.delta());
+        droplet.velocity.y -= DROPLET_GRAVITY * seconds;
+        transform.translation += (droplet.velocity * seconds).extend(0.);
+        sprite.color.set_a(droplet.timer.percent_left());
+        if droplet.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Counts down the player's breath while the head is under water, refills it at the surface
+pub(super) fn hold_breath(
+    time: Res<Time>,
+    mut damage: EventWriter<Damage>,
+    mut players: Query<(Entity, &Transform, &InWater, &mut Breath), With<Player>>,
+) {
+    for (player, transform, in_water, mut breath) in &mut players {
+        let Some(capacity) = in_water.water.breath else {
+            continue;
+        };
+        let head = transform.translation.y + PLAYER_HALF_HEIGHT;
+        if head > in_water.water.surface {
+            breath.0 = capacity;
+            continue;
+        }
+        breath.0 -= time.delta_seconds();
+        if breath.0 <= 0. {
+            damage.send(Damage::Hit {
+                target: player,
+                amount: 1,
+            });
+            breath.0 = DROWNING_INTERVAL;
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::FileStorage;
+#[cfg(target_arch = "wasm32")]
+pub use web::LocalStorage;
+
+/// Reads and writes text under a key
+/// Keys are plain names like `config` or `slot1`, each backend decides where they end up
+pub trait StorageBackend: Send + Sync + 'static {
+    /// Returns `Ok(None)` if nothing was written under `key` yet
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError>;
+
+    fn write(&self, key: &str, contents: &str) -> Result<(), StorageError>;
+}
+
+#[derive(Debug)]
+pub struct StorageError(pub String);
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// What is stored, native builds keep configs and save games in different directories
+#[derive(Copy, Clone, Debug)]
+pub enum Location {
+    Config,
+    Saves,
+}
+
+/// Persists RON serialized values with the backend of the current platform
+/// Failures are logged, the game keeps running with default values
+pub struct Storage {
+    backend: Option<Box<dyn StorageBackend>>,
+}
+
+impl Storage {
+    pub fn new(backend: impl StorageBackend) -> Self {
+        Storage {
+            backend: Some(Box::new(backend)),
+        }
+    }
+
+    /// The filesystem on native platforms, `localStorage` in the browser
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(location: Location) -> Self {
+        match FileStorage::for_location(location) {
+            Some(backend) => Storage::new(backend),
+            None => {
+                warn!("No {location:?} directory on this platform, nothing will be persisted");
+                Storage { backend: None }
+            }
+        }
+    }
+
+    /// The filesystem on native platforms, `localStorage` in the browser
+    #[cfg(target_arch = "wasm32")]
+    pub fn open(location: Location) -> Self {
+        Storage::new(LocalStorage::for_location(location))
+    }
+
+    /// Returns `None` if nothing is stored under `key` or the stored value can not be read
+    pub fn load<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let backend = self.backend.as_ref()?;
+        let contents = match backend.read(key) {
+            Ok(contents) => contents?,
+            Err(error) => {
+                warn!("Failed to read {key}: {error}");
+                return None;
+            }
+        };
+        match ron::from_str(&contents) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                warn!("Failed to parse {key}: {error}");
+                None
+            }
+        }
+    }
+
+    pub fn store<T: Serialize>(&self, key: &str, value: &T) {
+        let Some(backend) = self.backend.as_ref() else {
+            return;
+        };
+        let contents = match ron::ser::to_string_pretty(value, default()) {
+            Ok(contents) => contents,
+            Err(error) => {
+                warn!("Failed to serialize {key}: {error}");
+                return;
+            }
+        };
+        if let Err(error) = backend.write(key, &contents) {
+            warn!("Failed to write {key}: {error}");
+        }
+    }
+}
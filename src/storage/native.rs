@@ -0,0 +1,113 @@
+use crate::storage::{Location, StorageBackend, StorageError};
+use directories::ProjectDirs;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Stores every key as a `.ron` file in a directory
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileStorage { dir: dir.into() }
+    }
+
+    /// The platform's config or data directory of the game
+    pub fn for_location(location: Location) -> Option<Self> {
+        let dirs = ProjectDirs::from("me", "lfordyce", "SlaughterBerry")?;
+        let dir = match location {
+            Location::Config => dirs.config_dir().to_path_buf(),
+            Location::Saves => dirs.data_dir().join("saves"),
+        };
+        Some(FileStorage::new(dir))
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.ron"))
+    }
+}
+
+impl StorageBackend for FileStorage {
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        let path = self.path(key);
+        match fs::read_to_string(&path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(StorageError(format!("{path:?}: {error}"))),
+        }
+    }
+
+    fn write(&self, key: &str, contents: &str) -> Result<(), StorageError> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|error| StorageError(format!("{:?}: {error}", self.dir)))?;
+        let path = self.path(key);
+        fs::write(&path, contents).map_err(|error| StorageError(format!("{path:?}: {error}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Storage;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Value {
+        name: String,
+        count: u32,
+    }
+
+    /// An empty directory of its own for every test, removed again when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "slaughter_berry_storage_{name}_{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            TempDir(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn store_and_load_round_trip() {
+        let dir = TempDir::new("round_trip");
+        let storage = Storage::new(FileStorage::new(&dir.0));
+        let value = Value {
+            name: "berry".to_string(),
+            count: 3,
+        };
+
+        storage.store("slot1", &value);
+
+        assert_eq!(storage.load::<Value>("slot1"), Some(value));
+    }
+
+    #[test]
+    fn missing_key_loads_nothing() {
+        let dir = TempDir::new("missing");
+        let storage = Storage::new(FileStorage::new(&dir.0));
+
+        assert_eq!(storage.load::<Value>("slot1"), None);
+    }
+
+    #[test]
+    fn corrupt_file_loads_nothing() {
+        let dir = TempDir::new("corrupt");
+        fs::create_dir_all(&dir.0).unwrap();
+        fs::write(dir.0.join("slot1.ron"), "(name: \"berry\", count:").unwrap();
+        let storage = Storage::new(FileStorage::new(&dir.0));
+
+        assert_eq!(storage.load::<Value>("slot1"), None);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::storage::{Location, StorageBackend, StorageError};
+use web_sys::wasm_bindgen::JsValue;
+
+/// Stores every key as an entry in the browser's `localStorage`
+/// Entries are prefixed, so that other pages on the same origin do not clash with the game
+pub struct LocalStorage {
+    prefix: &'static str,
+}
+
+impl LocalStorage {
+    pub fn new(prefix: &'static str) -> Self {
+        LocalStorage { prefix }
+    }
+
+    pub fn for_location(location: Location) -> Self {
+        match location {
+            Location::Config => LocalStorage::new("slaughter_berry.config"),
+            Location::Saves => LocalStorage::new("slaughter_berry.saves"),
+        }
+    }
+
+    fn storage(&self) -> Result<web_sys::Storage, StorageError> {
+        web_sys::window()
+            .ok_or_else(|| StorageError("no browser window".to_string()))?
+            .local_storage()
+            .map_err(js_error)?
+            .ok_or_else(|| StorageError("localStorage is not available".to_string()))
+    }
+
+    fn item(&self, key: &str) -> String {
+        format!("{}.{key}", self.prefix)
+    }
+}
+
+fn js_error(error: JsValue) -> StorageError {
+    StorageError(format!("{error:?}"))
+}
+
+impl StorageBackend for LocalStorage {
+    fn read(&self, key: &str) -> Result<Option<String>, StorageError> {
+        self.storage()?.get_item(&self.item(key)).map_err(js_error)
+    }
+
+    fn write(&self, key: &str, contents: &str) -> Result<(), StorageError> {
+        self.storage()?
+            .set_item(&self.item(key), contents)
+            .map_err(js_error)
+    }
+}
@@ -0,0 +1,161 @@
+use crate::despawn::DespawnOnExit;
+use crate::dialogue::ActiveDialogue;
+use crate::ui::TEXT_COLOR;
+use crate::GameState;
+use bevy::prelude::*;
+
+/// More choices than this are not shown
+const MAX_CHOICES: usize = 4;
+const SELECTED_COLOR: Color = Color::rgb(1., 0.85, 0.3);
+
+pub struct DialogueBoxPlugin;
+
+/// This plugin draws the [`ActiveDialogue`] at the bottom of the screen
+/// The box is spawned hidden with the level and only shown while a dialogue is active
+impl Plugin for DialogueBoxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup_dialogue_box)
+            .add_systems(
+                Update,
+                update_dialogue_box.run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct DialogueBox;
+
+#[derive(Component)]
+struct DialoguePortrait;
+
+#[derive(Component)]
+struct DialogueSpeaker;
+
+#[derive(Component)]
+struct DialogueText;
+
+#[derive(Component)]
+struct DialogueChoiceText(usize);
+
+fn text_bundle(font_size: f32) -> TextBundle {
+    TextBundle::from_section(
+        "",
+        TextStyle {
+            font_size,
+            color: TEXT_COLOR,
+            ..default()
+        },
+    )
+}
+
+fn setup_dialogue_box(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(80.),
+                    min_height: Val::Vh(20.),
+                    left: Val::Percent(10.),
+                    bottom: Val::Vh(4.),
+                    padding: UiRect::all(Val::Vh(2.)),
+                    column_gap: Val::Vh(2.),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.8).into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            DialogueBox,
+            DespawnOnExit(GameState::Playing),
+        ))
+        .with_children(|dialogue_box| {
+            dialogue_box.spawn((
+                ImageBundle {
+                    style: Style {
+                        width: Val::Vh(14.),
+                        height: Val::Vh(14.),
+                        flex_shrink: 0.,
+                        ..default()
+                    },
+                    ..default()
+                },
+                DialoguePortrait,
+            ));
+            dialogue_box
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Vh(1.),
+                        flex_grow: 1.,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|column| {
+                    column.spawn((text_bundle(24.), DialogueSpeaker));
+                    column.spawn((text_bundle(28.), DialogueText));
+                    for index in 0..MAX_CHOICES {
+                        column.spawn((text_bundle(26.), DialogueChoiceText(index)));
+                    }
+                });
+        });
+}
+
+fn update_dialogue_box(
+    asset_server: Res<AssetServer>,
+    active: Option<Res<ActiveDialogue>>,
+    mut boxes: Query<&mut Visibility, With<DialogueBox>>,
+    mut portraits: Query<(&mut UiImage, &mut Style), With<DialoguePortrait>>,
+    mut speakers: Query<&mut Text, (With<DialogueSpeaker>, Without<DialogueText>)>,
+    mut texts: Query<&mut Text, (With<DialogueText>, Without<DialogueSpeaker>)>,
+    mut choices: Query<
+        (&DialogueChoiceText, &mut Text),
+        (Without<DialogueSpeaker>, Without<DialogueText>),
+    >,
+) {
+    let visibility = if active.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut dialogue_box in &mut boxes {
+        if *dialogue_box != visibility {
+            *dialogue_box = visibility;
+        }
+    }
+    let Some(active) = active else {
+        return;
+    };
+    if !active.is_changed() {
+        return;
+    }
+
+    let line = active.line();
+    for (mut image, mut style) in &mut portraits {
+        match line.and_then(|line| line.portrait.as_ref()) {
+            Some(portrait) => {
+                image.texture = asset_server.load(portrait.clone());
+                style.display = Display::Flex;
+            }
+            None => style.display = Display::None,
+        }
+    }
+    for mut text in &mut speakers {
+        text.sections[0].value = line.map(|line| line.speaker.clone()).unwrap_or_default();
+    }
+    for mut text in &mut texts {
+        text.sections[0].value = active.typed_text().to_string();
+    }
+    for (DialogueChoiceText(index), mut text) in &mut choices {
+        let section = &mut text.sections[0];
+        match active.choices().get(*index) {
+            Some(choice) => {
+                let selected = *index == active.selected();
+                section.value = format!("{} {}", if selected { ">" } else { " " }, choice.text);
+                section.style.color = if selected { SELECTED_COLOR } else { TEXT_COLOR };
+            }
+            None => section.value.clear(),
+        }
+    }
+}
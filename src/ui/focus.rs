@@ -0,0 +1,204 @@
+use crate::actions::{ControlInput, GameControl};
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+const CONFIRM_KEYS: [KeyCode; 2] = [KeyCode::Return, KeyCode::Space];
+const BACK_KEYS: [KeyCode; 2] = [KeyCode::Escape, KeyCode::Back];
+
+pub struct FocusPlugin;
+
+/// This plugin lets the player navigate menus with the keyboard or a gamepad
+/// One button of the top most [`MenuRoot`] is focused at a time and can be moved with the directional controls
+/// Confirming or going back presses a button the same way a mouse click does,
+/// so the click handlers of the menu screens work unchanged
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FocusedButton>()
+            .configure_sets(PreUpdate, UiNavigationLabel.after(UiSystem::Focus))
+            .add_systems(
+                PreUpdate,
+                (move_focus, press_focused_button)
+                    .chain()
+                    .in_set(UiNavigationLabel),
+            );
+    }
+}
+
+/// Keyboard and gamepad navigation of menus
+/// Screens that need the raw input for themselves can add run conditions to this set
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct UiNavigationLabel;
+
+/// Buttons below this node can be focused
+/// If several menus are shown, only the ones with the highest [`ZIndex`] are navigable
+#[derive(Component)]
+pub struct MenuRoot;
+
+/// The button pressed when going back in a menu, e.g. "Resume" or "Back"
+#[derive(Component)]
+pub struct BackButton;
+
+#[derive(Default, Resource)]
+pub struct FocusedButton(pub Option<Entity>);
+
+/// Buttons pressed from the keyboard or a gamepad during the last frame
+#[derive(Default)]
+struct PressedButtons(Vec<Entity>);
+
+fn z_order(z_index: &ZIndex) -> i32 {
+    match z_index {
+        ZIndex::Local(z) | ZIndex::Global(z) => *z,
+    }
+}
+
+/// Returns all buttons of the top most menus together with their on screen rect
+fn navigable_buttons(
+    roots: &Query<(Entity, &ZIndex), With<MenuRoot>>,
+    buttons: &Query<(Entity, &Node, &GlobalTransform), With<Button>>,
+    parents: &Query<&Parent>,
+) -> Vec<(Entity, Rect)> {
+    let Some(top) = roots.iter().map(|(_, z_index)| z_order(z_index)).max() else {
+        return vec![];
+    };
+    let is_active_root = |entity: Entity| {
+        roots
+            .get(entity)
+            .is_ok_and(|(_, z_index)| z_order(z_index) == top)
+    };
+    buttons
+        .iter()
+        // freshly spawned buttons are not laid out yet
+        .filter(|(_, node, _)| node.size() != Vec2::ZERO)
+        .filter(|(entity, _, _)| parents.iter_ancestors(*entity).any(&is_active_root))
+        .map(|(entity, node, transform)| (entity, node.logical_rect(transform)))
+        .collect()
+}
+
+fn direction(control_input: &ControlInput) -> Option<Vec2> {
+    // ui coordinates grow downwards
+    if control_input.just_pressed(GameControl::Up) {
+        Some(Vec2::NEG_Y)
+    } else if control_input.just_pressed(GameControl::Down) {
+        Some(Vec2::Y)
+    } else if control_input.just_pressed(GameControl::Left) {
+        Some(Vec2::NEG_X)
+    } else if control_input.just_pressed(GameControl::Right) {
+        Some(Vec2::X)
+    } else {
+        None
+    }
+}
+
+/// Finds the closest button in `direction`, preferring buttons that are in line with `from`
+fn next_in_direction(from: Rect, direction: Vec2, buttons: &[(Entity, Rect)]) -> Option<Entity> {
+    buttons
+        .iter()
+        .filter_map(|(entity, rect)| {
+            let offset = rect.center() - from.center();
+            let along = offset.dot(direction);
+            let across = offset.perp_dot(direction).abs();
+            (along > 0.).then_some((*entity, along + 2. * across))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+fn move_focus(
+    control_input: ControlInput,
+    mut focused: ResMut<FocusedButton>,
+    roots: Query<(Entity, &ZIndex), With<MenuRoot>>,
+    buttons: Query<(Entity, &Node, &GlobalTransform), With<Button>>,
+    parents: Query<&Parent>,
+    hovered: Query<(Entity, &Interaction), Changed<Interaction>>,
+) {
+    let navigable = navigable_buttons(&roots, &buttons, &parents);
+    let current = focused
+        .0
+        .and_then(|entity| navigable.iter().find(|(button, _)| *button == entity));
+
+    // the mouse takes the focus along with it
+    if let Some((entity, _)) = hovered.iter().find(|(entity, interaction)| {
+        **interaction == Interaction::Hovered
+            && navigable.iter().any(|(button, _)| button == entity)
+    }) {
+        focused.0 = Some(entity);
+        return;
+    }
+
+    let Some((_, rect)) = current else {
+        // focus the top left button of a newly opened menu
+        let first = navigable
+            .iter()
+            .min_by(|(_, a), (_, b)| {
+                a.min
+                    .y
+                    .total_cmp(&b.min.y)
+                    .then(a.min.x.total_cmp(&b.min.x))
+            })
+            .map(|(entity, _)| *entity);
+        if focused.0 != first {
+            focused.0 = first;
+        }
+        return;
+    };
+    if let Some(direction) = direction(&control_input) {
+        if let Some(next) = next_in_direction(*rect, direction, &navigable) {
+            focused.0 = Some(next);
+        }
+    }
+}
+
+fn press_focused_button(
+    mut pressed_buttons: Local<PressedButtons>,
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    mut gamepad_input: ResMut<Input<GamepadButton>>,
+    focused: Res<FocusedButton>,
+    roots: Query<(Entity, &ZIndex), With<MenuRoot>>,
+    buttons: Query<(Entity, &Node, &GlobalTransform), With<Button>>,
+    parents: Query<&Parent>,
+    back_buttons: Query<(), With<BackButton>>,
+    mut interactions: Query<&mut Interaction>,
+) {
+    // buttons are only held down for a single frame
+    for entity in pressed_buttons.0.drain(..) {
+        if let Ok(mut interaction) = interactions.get_mut(entity) {
+            if *interaction == Interaction::Pressed {
+                *interaction = Interaction::None;
+            }
+        }
+    }
+
+    let just_pressed = |keys: &[KeyCode], button_type: GamepadButtonType| {
+        keyboard_input.any_just_pressed(keys.iter().copied())
+            || gamepads
+                .iter()
+                .any(|gamepad| gamepad_input.just_pressed(GamepadButton::new(gamepad, button_type)))
+    };
+    let (target, keys, button_type) = if just_pressed(&CONFIRM_KEYS, GamepadButtonType::South) {
+        (focused.0, CONFIRM_KEYS, GamepadButtonType::South)
+    } else if just_pressed(&BACK_KEYS, GamepadButtonType::East) {
+        let back = navigable_buttons(&roots, &buttons, &parents)
+            .into_iter()
+            .map(|(entity, _)| entity)
+            .find(|entity| back_buttons.contains(*entity));
+        (back, BACK_KEYS, GamepadButtonType::East)
+    } else {
+        return;
+    };
+    let Some(entity) = target else {
+        return;
+    };
+    if let Ok(mut interaction) = interactions.get_mut(entity) {
+        *interaction = Interaction::Pressed;
+        pressed_buttons.0.push(entity);
+    }
+
+    // the press belongs to the menu, e.g. closing the pause menu with space must not jump
+    for key in keys {
+        keyboard_input.reset(key);
+    }
+    for gamepad in gamepads.iter() {
+        gamepad_input.reset(GamepadButton::new(gamepad, button_type));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::audio::Music;
+use crate::despawn::DespawnOnExit;
+use crate::ui::{spawn_button, BackButton, MenuRoot, TEXT_COLOR};
+use crate::GameState;
+use bevy::prelude::*;
+
+pub struct GameOverPlugin;
+
+/// This plugin shows the game over screen once the player ran out of lives
+/// The save keeps the level, so the next game starts at its beginning
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::GameOver), setup_game_over)
+            .add_systems(
+                Update,
+                click_game_over_button.run_if(in_state(GameState::GameOver)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct BackToMenu;
+
+fn setup_game_over(mut commands: Commands, mut music: ResMut<Music>) {
+    music.track = None;
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                ..default()
+            },
+            DespawnOnExit(GameState::GameOver),
+            MenuRoot,
+        ))
+        .with_children(|children| {
+            children.spawn(
+                TextBundle::from_section(
+                    "Game Over",
+                    TextStyle {
+                        font_size: 80.0,
+                        color: TEXT_COLOR,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(40.)),
+                    ..default()
+                }),
+            );
+            spawn_button(children, "Back to menu", 300., (BackToMenu, BackButton));
+        });
+}
+
+fn click_game_over_button(
+    mut next_state: ResMut<NextState<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<BackToMenu>)>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            next_state.set(GameState::Menu);
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use crate::cutscene::ActiveCutscene;
+use crate::despawn::DespawnOnExit;
+use crate::health::Health;
+use crate::loading::TextureAssets;
+use crate::player::{Abilities, Ability, DashCooldown, Player, Swing, ATTACK_DURATION, MAX_HEALTH};
+use crate::save::Progress;
+use crate::states::{BerryCount, GameplaySettings};
+use crate::ui::TEXT_COLOR;
+use crate::{GameState, GameplayLabel, PauseState};
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+use bevy::window::PrimaryWindow;
+
+/// Window height the HUD's font sizes are designed for
+const REFERENCE_HEIGHT: f32 = 720.;
+const FONT_SIZE: f32 = 28.;
+
+const HEART_FULL: Color = Color::WHITE;
+const HEART_EMPTY: Color = Color::rgba(0.3, 0.3, 0.3, 0.6);
+const COOLDOWN_COLOR: Color = Color::rgba(0.9, 0.9, 0.9, 0.8);
+
+pub struct HudPlugin;
+
+/// This plugin draws the heads-up display during `GameState::Playing`
+/// Node sizes are relative to the window height, font sizes follow along when the window is resized
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelTimer>()
+            .add_systems(OnEnter(GameState::Playing), (setup_hud, reset_level_timer))
+            .add_systems(Update, tick_level_timer.in_set(GameplayLabel))
+            .add_systems(
+                Update,
+                (
+                    update_hearts,
+                    update_lives_counter,
+                    update_berry_counter,
+                    update_level_timer,
+                    update_cooldowns,
+                    scale_hud_text,
+                    update_hud_visibility,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Time spent in the current level, not counting pauses
+#[derive(Default, Resource)]
+pub struct LevelTimer(pub Stopwatch);
+
+#[derive(Component)]
+struct Hud;
+
+#[derive(Component)]
+struct HudHeart(u32);
+
+/// Only shown if lives are enabled in the [`GameplaySettings`]
+#[derive(Component)]
+struct LivesCounter;
+
+#[derive(Component)]
+struct BerryCounter;
+
+#[derive(Component)]
+struct TimerText;
+
+/// Abilities with a cooldown shown in the HUD
+#[derive(Component, Clone, Copy)]
+enum HudCooldown {
+    Attack,
+    Dash,
+}
+
+impl HudCooldown {
+    /// The ability that has to be unlocked for the cooldown to be shown
+    fn ability(&self) -> Option<Ability> {
+        match self {
+            HudCooldown::Attack => None,
+            HudCooldown::Dash => Some(Ability::Dash),
+        }
+    }
+}
+
+/// The frame around a [`HudCooldown`]'s indicator
+#[derive(Component)]
+struct HudCooldownSlot(HudCooldown);
+
+/// Scales the font size of a HUD text with the window height
+#[derive(Component)]
+struct HudText;
+
+fn text_bundle(value: &str) -> impl Bundle {
+    (
+        TextBundle::from_section(
+            value,
+            TextStyle {
+                font_size: FONT_SIZE,
+                color: TEXT_COLOR,
+                ..default()
+            },
+        ),
+        HudText,
+    )
+}
+
+fn setup_hud(mut commands: Commands, textures: Res<TextureAssets>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexStart,
+                    padding: UiRect::all(Val::Vh(2.)),
+                    row_gap: Val::Vh(1.),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                ..default()
+            },
+            Hud,
+            DespawnOnExit(GameState::Playing),
+        ))
+        .with_children(|hud| {
+            hud.spawn(NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Vh(1.),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|hearts| {
+                for index in 0..MAX_HEALTH {
+                    hearts.spawn((
+                        ImageBundle {
+                            image: textures.heart.clone().into(),
+                            style: Style {
+                                width: Val::Vh(5.),
+                                height: Val::Vh(5.),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        HudHeart(index),
+                    ));
+                }
+                hearts.spawn((text_bundle(""), LivesCounter));
+            });
+            hud.spawn(NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Vh(1.),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|berries| {
+                berries.spawn(ImageBundle {
+                    image: textures.berry.clone().into(),
+                    style: Style {
+                        width: Val::Vh(4.),
+                        height: Val::Vh(4.),
+                        ..default()
+                    },
+                    ..default()
+                });
+                berries.spawn((text_bundle(""), BerryCounter));
+            });
+            hud.spawn(NodeBundle {
+                style: Style {
+                    column_gap: Val::Vh(1.),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|cooldowns| {
+                for cooldown in [HudCooldown::Attack, HudCooldown::Dash] {
+                    spawn_cooldown(cooldowns, cooldown);
+                }
+            });
+        });
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    top: Val::Vh(2.),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                ..default()
+            },
+            Hud,
+            DespawnOnExit(GameState::Playing),
+        ))
+        .with_children(|top| {
+            top.spawn((text_bundle(""), TimerText));
+        });
+}
+
+fn spawn_cooldown(parent: &mut ChildBuilder, cooldown: HudCooldown) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Vh(5.),
+                    height: Val::Vh(5.),
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.4).into(),
+                ..default()
+            },
+            HudCooldownSlot(cooldown),
+        ))
+        .with_children(|slot| {
+            slot.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(0.),
+                        ..default()
+                    },
+                    background_color: COOLDOWN_COLOR.into(),
+                    ..default()
+                },
+                cooldown,
+            ));
+        });
+}
+
+fn reset_level_timer(mut timer: ResMut<LevelTimer>) {
+    timer.0.reset();
+}
+
+fn tick_level_timer(time: Res<Time>, mut timer: ResMut<LevelTimer>) {
+    timer.0.tick(time.delta());
+}
+
+fn update_hearts(
+    players: Query<&Health, (With<Player>, Changed<Health>)>,
+    mut hearts: Query<(&HudHeart, &mut BackgroundColor)>,
+) {
+    let Ok(health) = players.get_single() else {
+        return;
+    };
+    for (heart, mut color) in &mut hearts {
+        color.0 = if heart.0 < health.0 {
+            HEART_FULL
+        } else {
+            HEART_EMPTY
+        };
+    }
+}
+
+fn update_lives_counter(
+    progress: Res<Progress>,
+    settings: Res<GameplaySettings>,
+    mut counters: Query<&mut Text, With<LivesCounter>>,
+) {
+    let value = if settings.lives {
+        format!("x{}", progress.lives)
+    } else {
+        String::new()
+    };
+    for mut text in &mut counters {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+fn update_berry_counter(
+    berry_count: Res<BerryCount>,
+    mut counters: Query<&mut Text, With<BerryCounter>>,
+) {
+    for mut text in &mut counters {
+        let value = format!("{} / {}", berry_count.collected, berry_count.total);
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+fn update_level_timer(
+    timer: Res<LevelTimer>,
+    settings: Res<GameplaySettings>,
+    mut texts: Query<&mut Text, With<TimerText>>,
+) {
+    let elapsed = timer.0.elapsed_secs();
+    let value = if settings.show_timer {
+        format!("{:02}:{:04.1}", (elapsed / 60.) as u32, elapsed % 60.)
+    } else {
+        String::new()
+    };
+    for mut text in &mut texts {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+fn update_cooldowns(
+    players: Query<(Option<&Swing>, Option<&DashCooldown>, &Abilities), With<Player>>,
+    mut slots: Query<(&HudCooldownSlot, &mut Style), Without<HudCooldown>>,
+    mut indicators: Query<(&HudCooldown, &mut Style)>,
+) {
+    let Ok((swing, dash_cooldown, abilities)) = players.get_single() else {
+        return;
+    };
+    for (slot, mut style) in &mut slots {
+        let display = match slot.0.ability() {
+            Some(ability) if !abilities.has(ability) => Display::None,
+            _ => Display::Flex,
+        };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+    for (cooldown, mut style) in &mut indicators {
+        let remaining = match cooldown {
+            HudCooldown::Attack => swing.map_or(0., |swing| 1. - swing.0 / ATTACK_DURATION),
+            HudCooldown::Dash => dash_cooldown.map_or(0., |cooldown| cooldown.0.percent_left()),
+        };
+        let height = Val::Percent(remaining.clamp(0., 1.) * 100.);
+        if style.height != height {
+            style.height = height;
+        }
+    }
+}
+
+fn scale_hud_text(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut texts: Query<&mut Text, With<HudText>>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let font_size = FONT_SIZE * window.height() / REFERENCE_HEIGHT;
+    for mut text in &mut texts {
+        if text.sections[0].style.font_size != font_size {
+            text.sections[0].style.font_size = font_size;
+        }
+    }
+}
+
+fn update_hud_visibility(
+    pause_state: Res<State<PauseState>>,
+    cutscene: Option<Res<ActiveCutscene>>,
+    mut huds: Query<&mut Visibility, With<Hud>>,
+) {
+    let visibility = match pause_state.get() {
+        PauseState::Running if cutscene.is_none() => Visibility::Inherited,
+        _ => Visibility::Hidden,
+    };
+    for mut hud in &mut huds {
+        if *hud != visibility {
+            *hud = visibility;
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::despawn::DespawnOnExit;
+use crate::save::{ActiveSlot, Progress, SaveGame, SaveStorage, SAVE_SLOTS};
+use crate::ui::{spawn_button, BackButton, MenuRoot, TEXT_COLOR};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+pub struct LoadPlugin;
+
+/// This plugin draws the save slot selection on top of the menu
+/// Picking a used slot continues the saved game, an empty slot starts a new one
+impl Plugin for LoadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<LoadMenu>()
+            .add_systems(OnEnter(LoadMenu::Open), setup_load_menu)
+            .add_systems(Update, click_load_button.run_if(in_state(LoadMenu::Open)));
+    }
+}
+
+#[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum LoadMenu {
+    #[default]
+    Closed,
+    Open,
+}
+
+#[derive(Component)]
+enum LoadButton {
+    Slot(usize),
+    Back,
+}
+
+fn slot_label(storage: &SaveStorage, slot: usize) -> String {
+    match storage.read_slot(slot) {
+        Some(save) => {
+            let progress = save.progress;
+            match progress.checkpoint {
+                Some(checkpoint) => format!(
+                    "Slot {} - {}, checkpoint {}",
+                    slot + 1,
+                    progress.level.name(),
+                    checkpoint + 1
+                ),
+                None => format!("Slot {} - {}", slot + 1, progress.level.name()),
+            }
+        }
+        None => format!("Slot {} - New game", slot + 1),
+    }
+}
+
+fn setup_load_menu(mut commands: Commands, storage: Res<SaveStorage>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.85).into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            DespawnOnExit(LoadMenu::Open),
+            MenuRoot,
+        ))
+        .with_children(|children| {
+            children.spawn(
+                TextBundle::from_section(
+                    "Load game",
+                    TextStyle {
+                        font_size: 50.0,
+                        color: TEXT_COLOR,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(20.)),
+                    ..default()
+                }),
+            );
+            for slot in 0..SAVE_SLOTS {
+                spawn_button(
+                    children,
+                    &slot_label(&storage, slot),
+                    600.,
+                    LoadButton::Slot(slot),
+                );
+            }
+            spawn_button(children, "Back", 140., (LoadButton::Back, BackButton));
+        });
+}
+
+fn click_load_button(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut next_load_state: ResMut<NextState<LoadMenu>>,
+    mut progress: ResMut<Progress>,
+    mut active_slot: ResMut<ActiveSlot>,
+    mut save_game: EventWriter<SaveGame>,
+    storage: Res<SaveStorage>,
+    interaction_query: Query<(&Interaction, &LoadButton), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            LoadButton::Slot(slot) => {
+                *progress = storage
+                    .read_slot(*slot)
+                    .map(|save| save.progress)
+                    .unwrap_or_default();
+                active_slot.0 = Some(*slot);
+                // new games show up in their slot right away
+                save_game.send(SaveGame);
+                next_load_state.set(LoadMenu::Closed);
+                next_state.set(GameState::Playing);
+            }
+            LoadButton::Back => next_load_state.set(LoadMenu::Closed),
+        }
+    }
+}
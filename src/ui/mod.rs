@@ -0,0 +1,106 @@
+use crate::ui::focus::FocusedButton;
+use bevy::prelude::*;
+
+mod dialogue_box;
+mod focus;
+mod game_over;
+mod hud;
+mod load;
+mod pause;
+mod settings;
+
+pub use focus::{BackButton, MenuRoot, UiNavigationLabel};
+pub use load::LoadMenu;
+pub use settings::SettingsMenu;
+
+pub const TEXT_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+
+pub struct UiPlugin;
+
+/// This plugin contains the building blocks shared by all menu screens
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            dialogue_box::DialogueBoxPlugin,
+            focus::FocusPlugin,
+            game_over::GameOverPlugin,
+            hud::HudPlugin,
+            load::LoadPlugin,
+            settings::SettingsPlugin,
+            pause::PausePlugin,
+        ))
+        .add_systems(Update, update_button_colors);
+    }
+}
+
+#[derive(Component)]
+pub struct ButtonColors {
+    pub normal: Color,
+    pub hovered: Color,
+    /// Shown while the button is focused for keyboard or gamepad navigation
+    pub focused: Color,
+}
+
+impl Default for ButtonColors {
+    fn default() -> Self {
+        ButtonColors {
+            normal: Color::rgb(0.15, 0.15, 0.15),
+            hovered: Color::rgb(0.25, 0.25, 0.25),
+            focused: Color::rgb(0.35, 0.35, 0.35),
+        }
+    }
+}
+
+/// Spawns a text button with the default [`ButtonColors`]
+/// `action` is inserted on the button and tells the screen's click handler what to do
+pub fn spawn_button(parent: &mut ChildBuilder, label: &str, width: f32, action: impl Bundle) {
+    let button_colors = ButtonColors::default();
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(width),
+                    height: Val::Px(50.0),
+                    margin: UiRect::all(Val::Px(5.)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: button_colors.normal.into(),
+                ..Default::default()
+            },
+            button_colors,
+            action,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: 40.0,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn update_button_colors(
+    focused: Res<FocusedButton>,
+    mut buttons: Query<(Entity, &Interaction, &mut BackgroundColor, &ButtonColors), With<Button>>,
+) {
+    for (entity, interaction, mut color, button_colors) in &mut buttons {
+        let target = if *interaction == Interaction::Hovered {
+            button_colors.hovered
+        } else if focused.0 == Some(entity) {
+            button_colors.focused
+        } else if *interaction == Interaction::Pressed {
+            // keep the color the button had when it got pressed
+            continue;
+        } else {
+            button_colors.normal
+        };
+        if color.0 != target {
+            color.0 = target;
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::actions::{ControlInput, GameControl};
+use crate::despawn::DespawnOnExit;
+use crate::states::RestartLevel;
+use crate::ui::{spawn_button, BackButton, ButtonColors, MenuRoot, SettingsMenu, TEXT_COLOR};
+use crate::{GameState, PauseState};
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use bevy_rapier2d::plugin::RapierConfiguration;
+
+pub struct PausePlugin;
+
+/// This plugin lets the player pause the game from the keyboard, a gamepad or the on screen pause button
+/// While `PauseState::Paused` is active the physics simulation is halted and the pause menu is drawn
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup_pause_button)
+            .add_systems(
+                Update,
+                (toggle_pause, click_pause_button).run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                OnEnter(PauseState::Paused),
+                (setup_pause_menu, pause_physics),
+            )
+            .add_systems(OnExit(PauseState::Paused), resume_physics)
+            .add_systems(OnExit(GameState::Playing), unpause);
+    }
+}
+
+#[derive(Component)]
+enum PauseButton {
+    Pause,
+    Resume,
+    Settings,
+    Restart,
+    Quit,
+}
+
+fn setup_pause_button(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(5.),
+                    right: Val::Px(5.),
+                    ..default()
+                },
+                ..default()
+            },
+            DespawnOnExit(GameState::Playing),
+        ))
+        .with_children(|children| {
+            children
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(50.0),
+                            height: Val::Px(50.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: Color::NONE.into(),
+                        ..default()
+                    },
+                    ButtonColors {
+                        normal: Color::NONE,
+                        ..default()
+                    },
+                    PauseButton::Pause,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "II",
+                        TextStyle {
+                            font_size: 40.0,
+                            color: TEXT_COLOR,
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
+
+fn setup_pause_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(5),
+                ..default()
+            },
+            DespawnOnExit(PauseState::Paused),
+            MenuRoot,
+        ))
+        .with_children(|children| {
+            children.spawn(
+                TextBundle::from_section(
+                    "Paused",
+                    TextStyle {
+                        font_size: 50.0,
+                        color: TEXT_COLOR,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(20.)),
+                    ..default()
+                }),
+            );
+            spawn_button(children, "Resume", 300., (PauseButton::Resume, BackButton));
+            spawn_button(children, "Settings", 300., PauseButton::Settings);
+            spawn_button(children, "Restart level", 300., PauseButton::Restart);
+            spawn_button(children, "Quit to menu", 300., PauseButton::Quit);
+        });
+}
+
+fn toggle_pause(
+    control_input: ControlInput,
+    pause_state: Res<State<PauseState>>,
+    settings_state: Res<State<SettingsMenu>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    if *settings_state.get() == SettingsMenu::Open
+        || !control_input.just_pressed(GameControl::Pause)
+    {
+        return;
+    }
+    match pause_state.get() {
+        PauseState::Running => next_pause_state.set(PauseState::Paused),
+        PauseState::Paused => next_pause_state.set(PauseState::Running),
+    }
+}
+
+fn click_pause_button(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut next_settings_state: ResMut<NextState<SettingsMenu>>,
+    mut restart_level: EventWriter<RestartLevel>,
+    interaction_query: Query<(&Interaction, &PauseButton), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            PauseButton::Pause => next_pause_state.set(PauseState::Paused),
+            PauseButton::Resume => next_pause_state.set(PauseState::Running),
+            PauseButton::Settings => next_settings_state.set(SettingsMenu::Open),
+            PauseButton::Restart => {
+                restart_level.send(RestartLevel);
+                next_pause_state.set(PauseState::Running);
+            }
+            PauseButton::Quit => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+fn pause_physics(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = false;
+    rapier_config.query_pipeline_active = false;
+}
+
+fn resume_physics(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = true;
+    rapier_config.query_pipeline_active = true;
+}
+
+fn unpause(mut next_pause_state: ResMut<NextState<PauseState>>) {
+    next_pause_state.set(PauseState::Running);
+}
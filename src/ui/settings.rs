@@ -0,0 +1,407 @@
+use crate::actions::{GameControl, InputBindings};
+use crate::audio::{AudioBus, AudioSettings};
+use crate::despawn::DespawnOnExit;
+use crate::states::GameplaySettings;
+use crate::ui::{spawn_button, BackButton, MenuRoot, UiNavigationLabel, TEXT_COLOR};
+use crate::video::{cycle, VideoSettings, RESOLUTIONS, UI_SCALES};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+const VOLUME_STEP: f32 = 0.1;
+
+pub struct SettingsPlugin;
+
+/// This plugin draws the settings screen on top of whatever is currently shown
+/// The screen is open while the State `SettingsMenu::Open` is active
+/// All changes are applied immediately, the config plugin saves them once the screen is closed
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<SettingsMenu>()
+            .init_resource::<SettingsTab>()
+            .init_resource::<AwaitingBinding>()
+            // the next key pressed is bound, instead of navigating the menu
+            .configure_sets(PreUpdate, UiNavigationLabel.run_if(not(awaiting_binding)))
+            .add_systems(OnEnter(SettingsMenu::Open), setup_settings)
+            .add_systems(
+                Update,
+                (
+                    capture_binding.run_if(awaiting_binding),
+                    switch_settings_tab.run_if(not(awaiting_binding)),
+                    click_settings_button,
+                    show_settings_tab.run_if(resource_changed::<SettingsTab>()),
+                    update_settings_labels,
+                )
+                    .chain()
+                    .run_if(in_state(SettingsMenu::Open)),
+            )
+            .add_systems(OnExit(SettingsMenu::Open), stop_awaiting_binding);
+    }
+}
+
+#[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum SettingsMenu {
+    #[default]
+    Closed,
+    Open,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Resource)]
+enum SettingsTab {
+    #[default]
+    Video,
+    Audio,
+    Game,
+    Controls,
+}
+
+impl SettingsTab {
+    const ALL: [SettingsTab; 4] = [
+        SettingsTab::Video,
+        SettingsTab::Audio,
+        SettingsTab::Game,
+        SettingsTab::Controls,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            SettingsTab::Video => "Video",
+            SettingsTab::Audio => "Audio",
+            SettingsTab::Game => "Game",
+            SettingsTab::Controls => "Controls",
+        }
+    }
+}
+
+/// The control that gets bound to the next key pressed
+#[derive(Default, Resource)]
+struct AwaitingBinding(Option<GameControl>);
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Setting {
+    DisplayMode,
+    Resolution,
+    Vsync,
+    UiScale,
+    Volume(AudioBus),
+    Muted,
+    Lives,
+    Timer,
+    Binding(GameControl),
+}
+
+impl Setting {
+    fn title(&self) -> &'static str {
+        match self {
+            Setting::DisplayMode => "Window",
+            Setting::Resolution => "Resolution",
+            Setting::Vsync => "VSync",
+            Setting::UiScale => "UI scale",
+            Setting::Timer => "Timer",
+            Setting::Volume(AudioBus::Master) => "Master",
+            Setting::Volume(AudioBus::Music) => "Music",
+            Setting::Volume(AudioBus::Sfx) => "Effects",
+            Setting::Muted => "Mute",
+            Setting::Lives => "Lives",
+            Setting::Binding(control) => control.name(),
+        }
+    }
+}
+
+#[derive(Component)]
+enum SettingsButton {
+    Tab(SettingsTab),
+    /// Step a setting forwards or backwards through its possible values
+    Adjust(Setting, i32),
+    Rebind(GameControl),
+    Back,
+}
+
+#[derive(Component)]
+struct SettingsLabel(Setting);
+
+#[derive(Component)]
+struct SettingsContent;
+
+fn on_off(value: bool) -> String {
+    let text = if value { "On" } else { "Off" };
+    text.to_string()
+}
+
+/// Everything shown in the settings screen
+#[derive(SystemParam)]
+struct SettingsValues<'w> {
+    audio: Res<'w, AudioSettings>,
+    video: Res<'w, VideoSettings>,
+    gameplay: Res<'w, GameplaySettings>,
+    bindings: Res<'w, InputBindings>,
+    awaiting_binding: Res<'w, AwaitingBinding>,
+}
+
+impl<'w> SettingsValues<'w> {
+    fn value(&self, setting: Setting) -> String {
+        match setting {
+            Setting::DisplayMode => self.video.display_mode.name().to_string(),
+            Setting::Resolution => {
+                format!("{}x{}", self.video.resolution.0, self.video.resolution.1)
+            }
+            Setting::Vsync => on_off(self.video.vsync),
+            Setting::UiScale => format!("{:.0}%", self.video.ui_scale * 100.),
+            Setting::Timer => on_off(self.gameplay.show_timer),
+            Setting::Volume(bus) => format!("{:.0}%", self.audio.level(bus) * 100.),
+            Setting::Muted => on_off(self.audio.muted),
+            Setting::Lives => on_off(self.gameplay.lives),
+            Setting::Binding(control) if self.awaiting_binding.0 == Some(control) => {
+                "Press a key".to_string()
+            }
+            Setting::Binding(control) => self
+                .bindings
+                .keys(control)
+                .iter()
+                .map(|key| format!("{key:?}"))
+                .collect::<Vec<_>>()
+                .join(" / "),
+        }
+    }
+}
+
+fn text_style() -> TextStyle {
+    TextStyle {
+        font_size: 30.0,
+        color: TEXT_COLOR,
+        ..default()
+    }
+}
+
+fn spawn_setting_row(parent: &mut ChildBuilder, setting: Setting, value: String) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn(
+                TextBundle::from_section(setting.title(), text_style()).with_style(Style {
+                    width: Val::Px(180.),
+                    ..default()
+                }),
+            );
+            let label = TextBundle::from_section(value, text_style()).with_style(Style {
+                width: Val::Px(200.),
+                ..default()
+            });
+            if let Setting::Binding(control) = setting {
+                row.spawn((label, SettingsLabel(setting)));
+                spawn_button(row, "Rebind", 140., SettingsButton::Rebind(control));
+            } else {
+                spawn_button(row, "<", 50., SettingsButton::Adjust(setting, -1));
+                row.spawn((label, SettingsLabel(setting)));
+                spawn_button(row, ">", 50., SettingsButton::Adjust(setting, 1));
+            }
+        });
+}
+
+fn spawn_tab_content(parent: &mut ChildBuilder, tab: SettingsTab, values: &SettingsValues) {
+    let settings: Vec<Setting> = match tab {
+        SettingsTab::Video => vec![
+            Setting::DisplayMode,
+            Setting::Resolution,
+            Setting::Vsync,
+            Setting::UiScale,
+        ],
+        SettingsTab::Audio => vec![
+            Setting::Volume(AudioBus::Master),
+            Setting::Volume(AudioBus::Music),
+            Setting::Volume(AudioBus::Sfx),
+            Setting::Muted,
+        ],
+        SettingsTab::Game => vec![Setting::Lives, Setting::Timer],
+        SettingsTab::Controls => GameControl::ALL.into_iter().map(Setting::Binding).collect(),
+    };
+    for setting in settings {
+        spawn_setting_row(parent, setting, values.value(setting));
+    }
+}
+
+fn setup_settings(mut commands: Commands, tab: Res<SettingsTab>, values: SettingsValues) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.85).into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            DespawnOnExit(SettingsMenu::Open),
+            MenuRoot,
+        ))
+        .with_children(|children| {
+            children.spawn(
+                TextBundle::from_section(
+                    "Settings",
+                    TextStyle {
+                        font_size: 50.0,
+                        color: TEXT_COLOR,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(20.)),
+                    ..default()
+                }),
+            );
+            children
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        margin: UiRect::bottom(Val::Px(20.)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|tabs| {
+                    for tab in SettingsTab::ALL {
+                        spawn_button(tabs, tab.name(), 200., SettingsButton::Tab(tab));
+                    }
+                });
+            children
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    SettingsContent,
+                ))
+                .with_children(|content| {
+                    spawn_tab_content(content, *tab, &values);
+                });
+            spawn_button(children, "Back", 140., (SettingsButton::Back, BackButton));
+        });
+}
+
+fn awaiting_binding(awaiting_binding: Res<AwaitingBinding>) -> bool {
+    awaiting_binding.0.is_some()
+}
+
+/// Cycle through the tabs with Q/E or the shoulder buttons of a gamepad
+fn switch_settings_tab(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut tab: ResMut<SettingsTab>,
+) {
+    let just_pressed = |key: KeyCode, button_type: GamepadButtonType| {
+        keyboard_input.just_pressed(key)
+            || gamepads
+                .iter()
+                .any(|gamepad| gamepad_input.just_pressed(GamepadButton::new(gamepad, button_type)))
+    };
+    let step = if just_pressed(KeyCode::Q, GamepadButtonType::LeftTrigger) {
+        -1
+    } else if just_pressed(KeyCode::E, GamepadButtonType::RightTrigger) {
+        1
+    } else {
+        return;
+    };
+    *tab = cycle(&SettingsTab::ALL, &*tab, step);
+}
+
+fn click_settings_button(
+    mut next_state: ResMut<NextState<SettingsMenu>>,
+    mut tab: ResMut<SettingsTab>,
+    mut audio: ResMut<AudioSettings>,
+    mut video: ResMut<VideoSettings>,
+    mut gameplay: ResMut<GameplaySettings>,
+    mut awaiting_binding: ResMut<AwaitingBinding>,
+    interaction_query: Query<(&Interaction, &SettingsButton), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            SettingsButton::Tab(selected) => {
+                if *tab != *selected {
+                    *tab = *selected;
+                }
+            }
+            SettingsButton::Adjust(setting, step) => match setting {
+                Setting::DisplayMode => video.display_mode = video.display_mode.cycle(*step),
+                Setting::Resolution => {
+                    video.resolution = cycle(&RESOLUTIONS, &video.resolution, *step);
+                }
+                Setting::Vsync => video.vsync = !video.vsync,
+                Setting::UiScale => video.ui_scale = cycle(&UI_SCALES, &video.ui_scale, *step),
+                Setting::Timer => gameplay.show_timer = !gameplay.show_timer,
+                Setting::Volume(bus) => {
+                    let level = audio.level(*bus) + VOLUME_STEP * *step as f32;
+                    audio.set_level(*bus, level);
+                }
+                Setting::Muted => audio.muted = !audio.muted,
+                Setting::Lives => gameplay.lives = !gameplay.lives,
+                Setting::Binding(_) => {}
+            },
+            SettingsButton::Rebind(control) => awaiting_binding.0 = Some(*control),
+            SettingsButton::Back => next_state.set(SettingsMenu::Closed),
+        }
+    }
+}
+
+fn capture_binding(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut awaiting_binding: ResMut<AwaitingBinding>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    let Some(key) = keyboard_input.get_just_pressed().next() else {
+        return;
+    };
+    if let Some(control) = awaiting_binding.0.take() {
+        bindings.rebind(control, *key);
+    }
+}
+
+fn stop_awaiting_binding(mut awaiting_binding: ResMut<AwaitingBinding>) {
+    awaiting_binding.0 = None;
+}
+
+fn show_settings_tab(
+    mut commands: Commands,
+    tab: Res<SettingsTab>,
+    values: SettingsValues,
+    content: Query<Entity, With<SettingsContent>>,
+) {
+    for entity in &content {
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .with_children(|content| {
+                spawn_tab_content(content, *tab, &values);
+            });
+    }
+}
+
+fn update_settings_labels(values: SettingsValues, mut labels: Query<(&mut Text, &SettingsLabel)>) {
+    for (mut text, label) in &mut labels {
+        let value = values.value(label.0);
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use bevy::window::{PresentMode, PrimaryWindow, WindowMode};
+use serde::{Deserialize, Serialize};
+
+pub const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+pub const UI_SCALES: [f32; 5] = [0.75, 1.0, 1.25, 1.5, 2.0];
+
+pub struct VideoPlugin;
+
+/// This plugin applies the [`VideoSettings`] to the primary window whenever they change
+impl Plugin for VideoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VideoSettings>().add_systems(
+            Update,
+            apply_video_settings.run_if(resource_changed::<VideoSettings>()),
+        );
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    const ALL: [DisplayMode; 3] = [
+        DisplayMode::Windowed,
+        DisplayMode::Borderless,
+        DisplayMode::Fullscreen,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "Windowed",
+            DisplayMode::Borderless => "Borderless",
+            DisplayMode::Fullscreen => "Fullscreen",
+        }
+    }
+
+    pub fn cycle(&self, step: i32) -> Self {
+        cycle(&DisplayMode::ALL, self, step)
+    }
+}
+
+#[derive(Clone, Debug, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoSettings {
+    pub display_mode: DisplayMode,
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    pub ui_scale: f32,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        VideoSettings {
+            display_mode: DisplayMode::Windowed,
+            resolution: RESOLUTIONS[0],
+            vsync: true,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+/// Steps `step` entries away from `current` in `options`, wrapping around at both ends
+/// Values that are not in `options` start from the first entry
+pub fn cycle<T: Copy + PartialEq>(options: &[T], current: &T, step: i32) -> T {
+    let index = options
+        .iter()
+        .position(|option| option == current)
+        .unwrap_or(0) as i32;
+    options[(index + step).rem_euclid(options.len() as i32) as usize]
+}
+
+fn apply_video_settings(
+    settings: Res<VideoSettings>,
+    mut ui_scale: ResMut<UiScale>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    ui_scale.0 = settings.ui_scale as f64;
+
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    window.present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+
+    // in the browser and on mobile the window size is dictated by the platform
+    if cfg!(any(
+        target_arch = "wasm32",
+        target_os = "android",
+        target_os = "ios"
+    )) {
+        return;
+    }
+    window.mode = match settings.display_mode {
+        DisplayMode::Windowed => WindowMode::Windowed,
+        DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+        DisplayMode::Fullscreen => WindowMode::Fullscreen,
+    };
+    window
+        .resolution
+        .set(settings.resolution.0 as f32, settings.resolution.1 as f32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycle_steps_forwards_and_backwards() {
+        assert_eq!(cycle(&UI_SCALES, &1.0, 1), 1.25);
+        assert_eq!(cycle(&UI_SCALES, &1.0, -1), 0.75);
+    }
+
+    #[test]
+    fn cycle_wraps_around_at_both_ends() {
+        assert_eq!(cycle(&RESOLUTIONS, &(2560, 1440), 1), (1280, 720));
+        assert_eq!(cycle(&RESOLUTIONS, &(1280, 720), -1), (2560, 1440));
+    }
+
+    #[test]
+    fn cycle_starts_from_first_entry_for_unknown_values() {
+        assert_eq!(cycle(&RESOLUTIONS, &(800, 600), 1), (1600, 900));
+    }
+
+    #[test]
+    fn display_mode_cycles_through_all_modes() {
+        assert_eq!(DisplayMode::Windowed.cycle(1), DisplayMode::Borderless);
+        assert_eq!(DisplayMode::Windowed.cycle(-1), DisplayMode::Fullscreen);
+    }
+}